@@ -0,0 +1,84 @@
+//! Expected answers, stored in an answers.toml file next to the input
+//! of each day:
+//!
+//! ```toml
+//! part1 = 989824
+//! part2 = 66432240
+//! ```
+//!
+//! A part without an expected answer is not checked.
+
+use serde::Deserialize;
+
+use std::fmt;
+use std::fs;
+use std::io;
+
+use common::{Answer, Error, Input, Result};
+
+/// The expected answer to one part of a puzzle.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Expected {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Int(num) => write!(f, "{}", num),
+            Expected::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// The expected answers to both parts of a puzzle.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedAnswers {
+    pub part1: Option<Expected>,
+    pub part2: Option<Expected>,
+}
+
+impl ExpectedAnswers {
+    /// Loads the expected answers stored at |path|, if any.
+    pub fn load(path: &str) -> Result<Option<ExpectedAnswers>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(Error::Io {
+                path: path.to_string(),
+                source,
+            }),
+        };
+
+        let input = Input::from_string(path, text);
+        toml::from_str(input.text())
+            .map(Some)
+            .map_err(|e| input.error_at_offset(e.span().map_or(0, |span| span.start), e.message()))
+    }
+}
+
+/// The outcome of checking the answer to one part.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail(Expected),
+    /// No answer is expected for the part.
+    Skip,
+}
+
+/// Checks |answer| against |expected|. Answers are compared by their
+/// textual representation, so that an integer may be expected as a
+/// string.
+pub fn check(expected: Option<&Expected>, answer: &Answer) -> Check {
+    match expected {
+        None =>
+            Check::Skip,
+        Some(expected) if expected.to_string() == answer.to_string() =>
+            Check::Pass,
+        Some(expected) =>
+            Check::Fail(expected.clone()),
+    }
+}
@@ -0,0 +1,108 @@
+//! Timing of the parsing and of each part of the solutions.
+
+use serde::Serialize;
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use common::{Input, Result, Solution};
+
+/// Statistics over the timings of one phase of a solution.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Stats {
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub p95_ns: u128,
+}
+
+impl Stats {
+    /// Computes the statistics of |samples|, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort_unstable();
+
+        Stats {
+            min_ns: samples[0].as_nanos(),
+            median_ns: percentile(samples, 50).as_nanos(),
+            p95_ns: percentile(samples, 95).as_nanos(),
+        }
+    }
+}
+
+/// Returns the |p|-th percentile of the sorted |samples|, using the
+/// nearest-rank method.
+fn percentile(samples: &[Duration], p: usize) -> Duration {
+    let rank = (p * samples.len()).div_ceil(100);
+    samples[rank.max(1) - 1]
+}
+
+/// The timings of a solution.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Times the parsing and both parts of |S| over |iterations| runs,
+/// |iterations| being at least 1.
+pub fn run<S: Solution>(input: &Input, iterations: usize) -> Result<Timings> {
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(input)?);
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed)?);
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed)?);
+        part2.push(start.elapsed());
+    }
+
+    Ok(Timings {
+        parse: Stats::from_samples(&mut parse),
+        part1: Stats::from_samples(&mut part1),
+        part2: Stats::from_samples(&mut part2),
+    })
+}
+
+/// Displays a duration given in nanoseconds with a readable unit.
+pub struct Nanos(pub u128);
+
+impl fmt::Display for Nanos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ns = self.0 as f64;
+        let text = if ns < 1e3 {
+            format!("{}ns", self.0)
+        } else if ns < 1e6 {
+            format!("{:.1}µs", ns / 1e3)
+        } else if ns < 1e9 {
+            format!("{:.1}ms", ns / 1e6)
+        } else {
+            format!("{:.2}s", ns / 1e9)
+        };
+
+        f.pad(&text)
+    }
+}
+
+/// The timings of a day, as reported in JSON.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct DayTimings {
+    pub day: u8,
+    #[serde(flatten)]
+    pub timings: Timings,
+}
+
+/// A benchmark report, as emitted in JSON.
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub iterations: usize,
+    pub days: Vec<DayTimings>,
+}
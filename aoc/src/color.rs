@@ -0,0 +1,36 @@
+//! Minimal ANSI coloring of the terminal output.
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+        }
+    }
+}
+
+/// Returns whether the output should be colored: stdout must be a
+/// terminal, and NO_COLOR must not be set.
+pub fn enabled() -> bool {
+    env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal()
+}
+
+/// Paints |text| in |color| if the output is colored.
+pub fn paint(text: &str, color: Color) -> String {
+    if enabled() {
+        format!("\x1b[{}m{}\x1b[0m", color.code(), text)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,109 @@
+use common::{solve, Answers, Input};
+
+use std::path::Path;
+use std::str::FromStr;
+
+pub mod answers;
+pub mod bench;
+pub mod color;
+
+/// A day of the calendar, as exposed to the runner.
+pub struct Day {
+    pub number: u8,
+    /// Parses the input and solves both parts.
+    pub solve: fn(&Input) -> common::Result<Answers>,
+    /// Times the parsing and both parts over a number of iterations.
+    pub bench: fn(&Input, usize) -> common::Result<bench::Timings>,
+}
+
+macro_rules! day {
+    ($number:expr, $solution:ty) => {
+        Day {
+            number: $number,
+            solve: solve::<$solution>,
+            bench: bench::run::<$solution>,
+        }
+    };
+}
+
+/// Returns the root of the workspace, which the default paths of the
+/// days are resolved from whatever the working directory.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent()
+        .expect("the runner is a member of the workspace")
+}
+
+impl Day {
+    /// Returns the directory of the day, relative to the workspace root.
+    pub fn dir(&self) -> String {
+        format!("day{:02}", self.number)
+    }
+
+    /// Returns the path of the file |name| in the directory of the day.
+    fn path(&self, name: &str) -> String {
+        workspace_root().join(self.dir()).join(name).to_string_lossy().into_owned()
+    }
+
+    /// Returns the default input path of the day.
+    pub fn input_path(&self) -> String {
+        self.path("input.txt")
+    }
+
+    /// Returns the path of the expected answers of the default input.
+    pub fn answers_path(&self) -> String {
+        self.path("answers.toml")
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day01::Day01),
+    day!(3, day03::Day03),
+    day!(4, day04::Day04),
+    day!(5, day05::Day05),
+    day!(6, day06::Day06),
+    day!(7, day07::Day07),
+    day!(8, day08::Day08),
+    day!(9, day09::Day09),
+    day!(10, day10::Day10),
+    day!(11, day11::Day11),
+];
+
+/// Finds the day numbered |number|, if it has been solved.
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Selects either a single day or all of them from the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Selection {
+    All,
+    One(u8),
+}
+
+impl Selection {
+    /// Returns the days covered by the selection.
+    pub fn days(&self) -> Result<Vec<&'static Day>, String> {
+        match self {
+            Selection::All =>
+                Ok(DAYS.iter().collect()),
+            Selection::One(number) =>
+                find_day(*number)
+                    .map(|day| vec![day])
+                    .ok_or_else(|| format!("day {} is not solved", number)),
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Selection::All);
+        }
+
+        s.parse::<u8>()
+            .map(Selection::One)
+            .map_err(|_| format!("expected a day number or 'all', got '{}'", s))
+    }
+}
@@ -0,0 +1,256 @@
+use clap::{Parser, Subcommand};
+
+use std::process;
+
+mod tools;
+
+use aoc::answers::{self, Check, ExpectedAnswers};
+use aoc::bench::{self, DayTimings, Nanos};
+use aoc::color::{paint, Color};
+use aoc::{Day, Selection};
+use common::{Answer, Input};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2020 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solver of a day, or of all days.
+    Run {
+        /// Day number, or 'all'.
+        day: Selection,
+        /// Input file, '-' for stdin, defaults to dayNN/input.txt.
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// Times the parsing and both parts of a day, or of all days.
+    Bench {
+        /// Day number, or 'all'.
+        day: Selection,
+        /// Input file, '-' for stdin, defaults to dayNN/input.txt.
+        #[arg(long)]
+        input: Option<String>,
+        /// Number of runs of each phase.
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        /// Emits the report in JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Finds expense report entries summing to a target.
+    Day01(tools::day01::Day01Args),
+    /// Counts the trees encountered along arbitrary slopes.
+    Day03(tools::day03::Day03Args),
+    /// Validates passports against declarative rules.
+    Day04(tools::day04::Day04Args),
+    /// Decodes and encodes boarding passes.
+    Day05(tools::day05::Day05Args),
+    /// Counts the questions answered by groups, with set algebra.
+    Day06(tools::day06::Day06Args),
+    /// Checks the answers of a day, or of all days, against the
+    /// expected ones stored in dayNN/answers.toml.
+    Verify {
+        /// Day number, or 'all'.
+        #[arg(default_value = "all")]
+        day: Selection,
+    },
+}
+
+/// The outcome of verifying a day.
+#[derive(Clone, Copy, PartialEq)]
+enum Verdict {
+    Pass,
+    Fail,
+    Skip,
+}
+
+/// Returns the selected days, along with the input of each of them.
+fn select_inputs(selection: Selection, input: Option<String>)
+        -> Result<Vec<(&'static Day, String)>, String> {
+    if selection == Selection::All && input.is_some() {
+        return Err("--input cannot be used with 'all'".to_string());
+    }
+
+    Ok(selection.days()?
+        .into_iter()
+        .map(|day| (day, input.clone().unwrap_or_else(|| day.input_path())))
+        .collect())
+}
+
+fn run(selection: Selection, input: Option<String>) -> Result<(), String> {
+    for (day, path) in select_inputs(selection, input)? {
+        let input = Input::load(&path)
+            .map_err(|e| e.to_string())?;
+        let answers = (day.solve)(&input)
+            .map_err(|e| format!("day {:02}: {}", day.number, e))?;
+
+        println!("day {:02}: part 1 = {}, part 2 = {}",
+            day.number, answers.part1, answers.part2);
+    }
+
+    Ok(())
+}
+
+fn bench(selection: Selection, input: Option<String>, iterations: usize,
+         json: bool) -> Result<(), String> {
+    if iterations == 0 {
+        return Err("--iterations must be at least 1".to_string());
+    }
+
+    let mut report = bench::Report {
+        iterations,
+        days: Vec::new(),
+    };
+
+    if !json {
+        println!("{:<6} {:<6} {:>10} {:>10} {:>10}", "day", "phase", "min", "median", "p95");
+    }
+
+    for (day, path) in select_inputs(selection, input)? {
+        let input = Input::load(&path)
+            .map_err(|e| e.to_string())?;
+        let timings = (day.bench)(&input, iterations)
+            .map_err(|e| format!("day {:02}: {}", day.number, e))?;
+
+        if !json {
+            let phases = [
+                ("parse", timings.parse),
+                ("part1", timings.part1),
+                ("part2", timings.part2),
+            ];
+            for (phase, stats) in phases.iter() {
+                println!("{:<6} {:<6} {:>10} {:>10} {:>10}", format!("{:02}", day.number),
+                    phase, Nanos(stats.min_ns), Nanos(stats.median_ns), Nanos(stats.p95_ns));
+            }
+        }
+
+        report.days.push(DayTimings {
+            day: day.number,
+            timings,
+        });
+    }
+
+    if json {
+        let text = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())?;
+        println!("{}", text);
+    }
+
+    Ok(())
+}
+
+fn print_status(day: &Day, part: &str, status: &str, color: Color, details: &str) {
+    let status = paint(&format!("{:<4}", status), color);
+    println!("day {:02} {:<6}  {}  {}", day.number, part, status, details);
+}
+
+fn verify_part(day: &Day, part: &str, expected: Option<&answers::Expected>,
+               answer: &Answer) -> Verdict {
+    match answers::check(expected, answer) {
+        Check::Pass => {
+            print_status(day, part, "ok", Color::Green, &answer.to_string());
+            Verdict::Pass
+        },
+        Check::Fail(expected) => {
+            let details = format!("got {}, expected {}", answer, expected);
+            print_status(day, part, "FAIL", Color::Red, &details);
+            Verdict::Fail
+        },
+        Check::Skip => {
+            print_status(day, part, "skip", Color::Yellow, "no expected answer");
+            Verdict::Skip
+        },
+    }
+}
+
+fn verify_day(day: &Day) -> Verdict {
+    let expected = match ExpectedAnswers::load(&day.answers_path()) {
+        Ok(Some(expected)) => expected,
+        Ok(None) => {
+            let details = format!("no {}", day.answers_path());
+            print_status(day, "", "skip", Color::Yellow, &details);
+            return Verdict::Skip;
+        },
+        Err(e) => {
+            print_status(day, "", "FAIL", Color::Red, &e.to_string());
+            return Verdict::Fail;
+        },
+    };
+
+    let answers = match Input::load(&day.input_path()).and_then(|input| (day.solve)(&input)) {
+        Ok(answers) => answers,
+        Err(e) => {
+            print_status(day, "", "FAIL", Color::Red, &e.to_string());
+            return Verdict::Fail;
+        },
+    };
+
+    let verdicts = [
+        verify_part(day, "part 1", expected.part1.as_ref(), &answers.part1),
+        verify_part(day, "part 2", expected.part2.as_ref(), &answers.part2),
+    ];
+
+    if verdicts.contains(&Verdict::Fail) {
+        Verdict::Fail
+    } else if verdicts.contains(&Verdict::Pass) {
+        Verdict::Pass
+    } else {
+        Verdict::Skip
+    }
+}
+
+/// Verifies the selected days, and returns whether none failed.
+fn verify(selection: Selection) -> Result<bool, String> {
+    let verdicts: Vec<Verdict> = selection.days()?
+        .into_iter()
+        .map(verify_day)
+        .collect();
+
+    let count = |verdict| verdicts.iter().filter(|v| **v == verdict).count();
+    let num_failed = count(Verdict::Fail);
+    println!();
+    println!("{}, {}, {}",
+        paint(&format!("{} passed", count(Verdict::Pass)), Color::Green),
+        paint(&format!("{} failed", num_failed),
+            if num_failed > 0 { Color::Red } else { Color::Green }),
+        paint(&format!("{} skipped", count(Verdict::Skip)), Color::Yellow));
+
+    Ok(num_failed == 0)
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let res = match cli.command {
+        Command::Run { day, input } =>
+            run(day, input).map(|_| true),
+        Command::Bench { day, input, iterations, json } =>
+            bench(day, input, iterations, json).map(|_| true),
+        Command::Day01(args) =>
+            tools::day01::run(&args).map(|_| true),
+        Command::Day03(args) =>
+            tools::day03::run(&args).map(|_| true),
+        Command::Day04(args) =>
+            tools::day04::run(&args).map(|_| true),
+        Command::Day05(args) =>
+            tools::day05::run(&args).map(|_| true),
+        Command::Day06(args) =>
+            tools::day06::run(&args).map(|_| true),
+        Command::Verify { day } =>
+            verify(day),
+    };
+
+    match res {
+        Ok(true) => {},
+        Ok(false) =>
+            process::exit(1),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        },
+    }
+}
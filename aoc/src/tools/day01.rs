@@ -0,0 +1,95 @@
+use clap::Args;
+
+use common::Solution;
+use day01::subset_sum;
+use day01::Day01;
+
+#[derive(Args)]
+pub struct Day01Args {
+    /// Input file, '-' for stdin, defaults to day01/input.txt.
+    #[arg(long)]
+    pub input: Option<String>,
+    /// Sum the entries must add up to.
+    #[arg(long, default_value_t = day01::TARGET, allow_negative_numbers = true)]
+    pub target: i64,
+    /// Number of entries to combine.
+    #[arg(short, long = "size", default_value_t = 2)]
+    pub k: usize,
+    /// Lists every distinct combination instead of the first one.
+    #[arg(long)]
+    pub all: bool,
+    /// Only prints the number of distinct combinations.
+    #[arg(long, conflicts_with = "all")]
+    pub count: bool,
+    /// Looks for subsets of any size instead of exactly k entries.
+    #[arg(long, conflicts_with_all = ["all", "count"])]
+    pub subset: bool,
+    /// Memory budget of the subset search, in MiB.
+    #[arg(long, default_value_t = subset_sum::DEFAULT_MEMORY_BUDGET >> 20)]
+    pub memory_budget: usize,
+}
+
+/// Formats |nums| as a sum, e.g. "1721 + 299".
+fn format_sum(nums: &[i64]) -> String {
+    let terms: Vec<String> = nums.iter().map(|num| num.to_string()).collect();
+    terms.join(" + ")
+}
+
+/// Prints |combination| along with its product.
+fn print_combination(combination: &[i64], target: i64) {
+    let product = match day01::product(combination) {
+        Some(product) => product.to_string(),
+        None => "overflow".to_string(),
+    };
+    println!("{} = {} (product: {})", format_sum(combination), target, product);
+}
+
+fn run_subset(args: &Day01Args, nums: &[i64]) -> Result<(), String> {
+    let budget = args.memory_budget.saturating_mul(1 << 20);
+    let exists = subset_sum::exists(args.target, nums, budget)
+        .map_err(|e| e.to_string())?;
+    println!("subset summing to {}: {}", args.target, if exists { "yes" } else { "no" });
+    if !exists {
+        return Ok(());
+    }
+
+    let report = subset_sum::solve(args.target, nums, budget)
+        .map_err(|e| e.to_string())?;
+    if let Some(smallest) = &report.smallest {
+        print!("smallest subset ({} entries): ", smallest.len());
+        print_combination(smallest, args.target);
+    }
+    match report.count {
+        Some(count) => println!("number of subsets: {}", count),
+        None => println!("number of subsets: at least {}", u128::MAX),
+    }
+
+    Ok(())
+}
+
+pub fn run(args: &Day01Args) -> Result<(), String> {
+    let input = super::load_input(1, args.input.as_deref())?;
+    let nums = Day01::parse(&input).map_err(|e| e.to_string())?;
+
+    if args.subset {
+        return run_subset(args, &nums);
+    }
+
+    if args.count {
+        println!("{}", day01::combinations(args.target, args.k, &nums).count());
+        return Ok(());
+    }
+
+    if args.all {
+        for combination in day01::combinations(args.target, args.k, &nums) {
+            print_combination(&combination, args.target);
+        }
+        return Ok(());
+    }
+
+    let combination = day01::solve_k(args.target, args.k, &nums)
+        .ok_or_else(|| format!("no {} entries sum to {}", args.k, args.target))?;
+    print_combination(&combination, args.target);
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use clap::Args;
+use serde::Serialize;
+
+use std::fs;
+
+use aoc::color;
+use common::{Input, Solution};
+use day03::path;
+use day03::search::{self, Ranked};
+use day03::{Day03, Map, Slope};
+
+#[derive(Args)]
+pub struct Day03Args {
+    /// Input file, '-' for stdin, defaults to day03/input.txt.
+    #[arg(long)]
+    pub input: Option<String>,
+    /// Slope to follow, as "dx,dy" steps or as "dx/dy" squares right per
+    /// square down; a negative dx goes left. Defaults to the slopes of
+    /// the second part.
+    #[arg(long = "slope", allow_hyphen_values = true)]
+    pub slopes: Vec<Slope>,
+    /// File listing slopes to follow, one per line.
+    #[arg(long)]
+    pub slopes_file: Option<String>,
+    /// Ranks every slope within the dx and dy bounds by trees
+    /// encountered instead.
+    #[arg(long, conflicts_with_all = ["slopes", "slopes_file"])]
+    pub search: bool,
+    /// Smallest dx of the searched slopes.
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+    pub min_dx: i64,
+    /// Largest dx of the searched slopes.
+    #[arg(long, default_value_t = 10, allow_negative_numbers = true)]
+    pub max_dx: i64,
+    /// Smallest dy of the searched slopes.
+    #[arg(long, default_value_t = 1)]
+    pub min_dy: usize,
+    /// Largest dy of the searched slopes.
+    #[arg(long, default_value_t = 3)]
+    pub max_dy: usize,
+    /// Number of searched slopes to print, all of them if unset.
+    #[arg(long)]
+    pub top: Option<usize>,
+    /// Finds the path to the bottom of the map encountering the fewest
+    /// trees instead, and draws it over the map.
+    #[arg(long, conflicts_with_all = ["slopes", "slopes_file", "search"])]
+    pub path: bool,
+    /// Move allowed at each step of the path, as "dx,dy". Defaults to
+    /// one square down, and up to one square left or three right.
+    #[arg(long = "step", allow_hyphen_values = true, requires = "path")]
+    pub steps: Vec<Slope>,
+    /// Draws the squares visited along the slopes over the map instead.
+    #[arg(long, conflicts_with_all = ["search", "path"])]
+    pub draw: bool,
+    /// Writes an SVG drawing of the squares visited along the slopes
+    /// to this file.
+    #[arg(long, conflicts_with_all = ["search", "path"])]
+    pub svg: Option<String>,
+    /// Emits the report in JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Serialize)]
+struct SlopeTrees {
+    dx: i64,
+    dy: usize,
+    trees: u64,
+}
+
+#[derive(Serialize)]
+struct SearchEntry {
+    dx: i64,
+    dy: usize,
+    trees: u64,
+    steps: usize,
+}
+
+impl From<&Ranked> for SearchEntry {
+    fn from(ranked: &Ranked) -> SearchEntry {
+        SearchEntry {
+            dx: ranked.slope.dx(),
+            dy: ranked.slope.dy(),
+            trees: ranked.trees,
+            steps: ranked.steps,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PathReport {
+    trees: u64,
+    squares: Vec<(i64, usize)>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    slopes: Vec<SlopeTrees>,
+    /// None when the product overflows.
+    product: Option<u64>,
+}
+
+fn run_search(args: &Day03Args, map: &Map) -> Result<(), String> {
+    let mut ranking = search::search(map, args.min_dx..=args.max_dx, args.min_dy..=args.max_dy)
+        .map_err(|e| e.to_string())?;
+    if let Some(top) = args.top {
+        ranking.truncate(top);
+    }
+
+    if args.json {
+        let entries: Vec<SearchEntry> = ranking.iter().map(SearchEntry::from).collect();
+        let text = serde_json::to_string_pretty(&entries)
+            .map_err(|e| e.to_string())?;
+        println!("{}", text);
+        return Ok(());
+    }
+
+    println!("{:>4} {:<10} {:>10} {:>10}", "rank", "slope", "trees", "steps");
+    for (i, ranked) in ranking.iter().enumerate() {
+        println!("{:>4} {:<10} {:>10} {:>10}", i + 1, ranked.slope, ranked.trees, ranked.steps);
+    }
+
+    Ok(())
+}
+
+fn run_path(args: &Day03Args, map: &Map) -> Result<(), String> {
+    let moves = if args.steps.is_empty() { path::default_moves() } else { args.steps.clone() };
+    let path = path::find_path(map, &moves)
+        .map_err(|e| e.to_string())?;
+
+    if args.json {
+        let report = PathReport {
+            trees: path.trees,
+            squares: path.squares,
+        };
+        let text = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())?;
+        println!("{}", text);
+        return Ok(());
+    }
+
+    print!("{}", path.render(map, color::enabled()));
+    println!("trees: {}, steps: {}", path.trees, path.squares.len());
+
+    Ok(())
+}
+
+pub fn run(args: &Day03Args) -> Result<(), String> {
+    let input = super::load_input(3, args.input.as_deref())?;
+    let map = Day03::parse(&input).map_err(|e| e.to_string())?;
+
+    if args.search {
+        return run_search(args, &map);
+    }
+    if args.path {
+        return run_path(args, &map);
+    }
+
+    let mut slopes = args.slopes.clone();
+    if let Some(path) = &args.slopes_file {
+        let file = Input::load(path).map_err(|e| e.to_string())?;
+        slopes.extend(day03::parse_slopes(&file).map_err(|e| e.to_string())?);
+    }
+    if slopes.is_empty() {
+        slopes = day03::SLOPES.to_vec();
+    }
+
+    if args.draw || args.svg.is_some() {
+        let paths: Vec<Vec<(i64, usize)>> = slopes.iter()
+            .map(|slope| map.traverse(*slope))
+            .collect();
+        if let Some(path) = &args.svg {
+            fs::write(path, map.render_svg(&paths))
+                .map_err(|e| format!("{}: {}", path, e))?;
+        }
+        if args.draw {
+            print!("{}", map.render_ascii(&paths, color::enabled()));
+        }
+        return Ok(());
+    }
+
+    let report = Report {
+        slopes: slopes.iter()
+            .map(|slope| SlopeTrees {
+                dx: slope.dx(),
+                dy: slope.dy(),
+                trees: map.count_trees(*slope),
+            })
+            .collect(),
+        product: slopes.iter()
+            .try_fold(1u64, |acc, slope| acc.checked_mul(map.count_trees(*slope))),
+    };
+
+    if args.json {
+        let text = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())?;
+        println!("{}", text);
+        return Ok(());
+    }
+
+    println!("{:<10} {:>10}", "slope", "trees");
+    for entry in &report.slopes {
+        println!("{:<10} {:>10}", format!("{},{}", entry.dx, entry.dy), entry.trees);
+    }
+    match report.product {
+        Some(product) => println!("{:<10} {:>10}", "product", product),
+        None => println!("{:<10} {:>10}", "product", "overflow"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use clap::{Args, ValueEnum};
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::thread;
+
+use common::input::RecordReader;
+use common::Input;
+use day04::stream::{self, Checked};
+use day04::{Mode, Parser, Report, Schema};
+
+#[derive(Args)]
+pub struct Day04Args {
+    /// Input file, '-' for stdin, defaults to day04/input.txt.
+    #[arg(long)]
+    pub input: Option<String>,
+    /// Validation rules, in TOML or in JSON when the file name ends
+    /// with '.json'. Defaults to the rules of the puzzle.
+    #[arg(long)]
+    pub schema: Option<String>,
+    /// Reports unknown, duplicate and malformed fields instead of
+    /// silently accepting them or skipping their record, and fails if
+    /// there are any.
+    #[arg(long)]
+    pub strict: bool,
+    /// Writes the passports of the given kind in canonical form instead
+    /// of counting them: each on a single line, followed by a blank
+    /// line.
+    #[arg(long, value_enum, conflicts_with = "report")]
+    pub filter: Option<Filter>,
+    /// File the filtered passports are written to, defaults to stdout.
+    #[arg(long, requires = "filter")]
+    pub output: Option<String>,
+    /// Explains why each invalid passport is invalid.
+    #[arg(long)]
+    pub report: bool,
+    /// Emits the report in JSON.
+    #[arg(long, requires = "report")]
+    pub json: bool,
+    /// Number of threads validating the passports, 0 for one per CPU.
+    /// Passports are streamed, so inputs larger than memory are fine.
+    #[arg(long, default_value_t = 1)]
+    pub threads: usize,
+}
+
+/// The passports kept by --filter.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Filter {
+    /// Passports with every required field.
+    Complete,
+    /// Passports missing a required field.
+    Incomplete,
+    /// Passports whose every field is valid.
+    Valid,
+    /// Passports with a missing or invalid field.
+    Invalid,
+}
+
+impl Filter {
+    fn keeps(self, checked: &Checked) -> bool {
+        match self {
+            Filter::Complete => checked.complete,
+            Filter::Incomplete => !checked.complete,
+            Filter::Valid => checked.is_valid(),
+            Filter::Invalid => !checked.is_valid(),
+        }
+    }
+}
+
+/// Opens the file the filtered passports are written to.
+fn open_output(path: Option<&str>) -> Result<Box<dyn Write>, String> {
+    match path {
+        Some(path) => File::create(path)
+            .map(|file| Box::new(BufWriter::new(file)) as Box<dyn Write>)
+            .map_err(|e| format!("{}: {}", path, e)),
+        None => Ok(Box::new(BufWriter::new(io::stdout()))),
+    }
+}
+
+pub fn run(args: &Day04Args) -> Result<(), String> {
+    let loaded;
+    let schema = match &args.schema {
+        Some(path) => {
+            let file = Input::load(path).map_err(|e| e.to_string())?;
+            loaded = Schema::parse(&file).map_err(|e| e.to_string())?;
+            &loaded
+        },
+        None => Schema::default_schema(),
+    };
+
+    let threads = match args.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let mode = if args.strict { Mode::Strict } else { Mode::Lenient };
+    let parser = Parser::new(schema, mode);
+
+    let (path, reader) = super::open_input(4, args.input.as_deref())?;
+    let records = RecordReader::new(&path, reader);
+
+    let mut out = match args.filter {
+        Some(_) => Some(open_output(args.output.as_deref())?),
+        None => None,
+    };
+    let write_error = |e: io::Error| match &args.output {
+        Some(path) => format!("{}: {}", path, e),
+        None => e.to_string(),
+    };
+
+    let mut report = Report::default();
+    let mut num_passports = 0;
+    let mut num_complete = 0;
+    let mut num_valid = 0;
+    let mut num_diagnostics = 0;
+
+    for checked in stream::check_records(&parser, records, threads) {
+        let checked = checked.map_err(|e| e.to_string())?;
+        for diagnostic in &checked.diagnostics {
+            eprintln!("{}:{}", path, diagnostic);
+        }
+        num_diagnostics += checked.diagnostics.len();
+
+        let passport = match &checked.passport {
+            Some(passport) => passport,
+            None => {
+                if args.report {
+                    report.skip(checked.record, checked.first_line, checked.last_line);
+                }
+                continue;
+            },
+        };
+        num_passports += 1;
+        if checked.complete {
+            num_complete += 1;
+        }
+        if checked.is_valid() {
+            num_valid += 1;
+        }
+
+        if let (Some(filter), Some(out)) = (args.filter, &mut out) {
+            if filter.keeps(&checked) {
+                writeln!(out, "{}\n", passport.to_canonical(schema))
+                    .map_err(write_error)?;
+            }
+        }
+        // Only the report keeps track of past passports.
+        if args.report {
+            report.add(passport, checked.failures);
+        }
+    }
+
+    if let Some(out) = &mut out {
+        out.flush().map_err(write_error)?;
+    } else if args.report && args.json {
+        let text = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())?;
+        println!("{}", text);
+    } else if args.report {
+        print!("{}", report);
+    } else {
+        println!("passports: {}", num_passports);
+        println!("complete (part 1): {}", num_complete);
+        println!("valid (part 2): {}", num_valid);
+    }
+
+    if num_diagnostics > 0 {
+        return Err(format!("found {} malformed, unknown or duplicate fields", num_diagnostics));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use clap::Args;
+
+use aoc::color;
+
+use day05::flight::Stats;
+use day05::{Codec, Flight, Seat};
+
+#[derive(Args)]
+pub struct Day05Args {
+    /// Input file, '-' for stdin, defaults to day05/input.txt.
+    #[arg(long)]
+    pub input: Option<String>,
+    /// Number of rows of the plane, a power of two.
+    #[arg(long, default_value_t = 128)]
+    pub rows: usize,
+    /// Number of seats per row, a power of two.
+    #[arg(long, default_value_t = 8)]
+    pub cols: usize,
+    /// Letters picking the front and back halves of the rows.
+    #[arg(long, default_value = "FB")]
+    pub row_letters: String,
+    /// Letters picking the left and right halves of the columns.
+    #[arg(long, default_value = "LR")]
+    pub col_letters: String,
+    /// Decodes letters regardless of their case.
+    #[arg(long)]
+    pub ignore_case: bool,
+    /// First row of the plane that exists, seats before it being
+    /// reported.
+    #[arg(long)]
+    pub first_row: Option<usize>,
+    /// Last row of the plane that exists, seats after it being reported.
+    #[arg(long)]
+    pub last_row: Option<usize>,
+    /// Boarding pass to decode.
+    #[arg(long)]
+    pub decode: Vec<String>,
+    /// Seat to encode into a boarding pass, as "row,col".
+    #[arg(long)]
+    pub encode: Vec<String>,
+    /// Lists every free seat of the input between two taken ones.
+    #[arg(long)]
+    pub gaps: bool,
+    /// Draws the seat map of the input.
+    #[arg(long)]
+    pub map: bool,
+    /// Column before which the seat map has an aisle, defaults to the
+    /// middle of the rows.
+    #[arg(long = "aisle")]
+    pub aisles: Vec<usize>,
+    /// Prints statistics about the seats taken.
+    #[arg(long)]
+    pub stats: bool,
+    /// Checks the passes of the input for duplicates, passes of the
+    /// same seat and seats out of the rows of the plane.
+    #[arg(long)]
+    pub check: bool,
+    /// Fails if any pass of the input is a duplicate, shares its seat or
+    /// is out of the rows of the plane.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+/// Parses a pair of letters, such as "FB".
+fn parse_letters(name: &str, letters: &str) -> Result<[char; 2], String> {
+    match letters.chars().collect::<Vec<char>>()[..] {
+        [low, high] => Ok([low, high]),
+        _ => Err(format!("--{} must be two letters, not '{}'", name, letters)),
+    }
+}
+
+/// Parses a seat, such as "70,7".
+fn parse_seat(text: &str) -> Result<Seat, String> {
+    let (row, col) = text.split_once(',')
+        .ok_or_else(|| format!("seat must be 'row,col', not '{}'", text))?;
+    let row = row.trim().parse::<usize>().map_err(|e| format!("{}: {}", text, e))?;
+    let col = col.trim().parse::<usize>().map_err(|e| format!("{}: {}", text, e))?;
+    Ok(Seat::from(row, col))
+}
+
+/// Creates the codec of the plane described by |args|.
+pub fn codec(args: &Day05Args) -> Result<Codec, String> {
+    let row_letters = parse_letters("row-letters", &args.row_letters)?;
+    let col_letters = parse_letters("col-letters", &args.col_letters)?;
+    let codec = Codec::new(args.rows, args.cols, row_letters, col_letters)
+        .map_err(|e| e.to_string())?;
+    Ok(if args.ignore_case { codec.ignoring_case() } else { codec })
+}
+
+fn print_stats(stats: &Stats) {
+    println!("passes: {}, seats taken: {}", stats.num_passes, stats.num_taken);
+    match (stats.first_row, stats.last_row) {
+        (Some(first), Some(last)) => println!("occupied rows: {} to {}", first, last),
+        _ => println!("occupied rows: none"),
+    }
+
+    println!("{:>5} {:>6}", "row", "taken");
+    for (row, taken) in stats.taken_per_row.iter().enumerate() {
+        if *taken > 0 {
+            println!("{:>5} {:>6}", row, taken);
+        }
+    }
+
+    if stats.duplicates.is_empty() {
+        println!("duplicate passes: none");
+    }
+    for (pass, lines) in &stats.duplicates {
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        println!("duplicate pass {} on lines {}", pass, lines.join(", "));
+    }
+}
+
+pub fn run(args: &Day05Args) -> Result<(), String> {
+    let codec = codec(args)?;
+
+    for pass in &args.decode {
+        let id = codec.decode_id(pass)
+            .map_err(|e| format!("{}: {}", pass, e.at(pass)))?;
+        let seat = codec.seat(id);
+        println!("{}: row {}, column {}, id {}", pass, seat.row(), seat.col(), id);
+    }
+
+    for text in &args.encode {
+        let seat = parse_seat(text)?;
+        let pass = codec.encode(&seat)
+            .map_err(|e| e.to_string())?;
+        println!("row {}, column {}: {}", seat.row(), seat.col(), pass);
+    }
+
+    if !args.map && !args.stats && !args.gaps && !args.check && !args.strict {
+        return Ok(());
+    }
+
+    let input = super::load_input(5, args.input.as_deref())?;
+    let rows = match (args.first_row, args.last_row) {
+        (None, None) => None,
+        (first, last) => Some(first.unwrap_or(0)..=last.unwrap_or(codec.rows() - 1)),
+    };
+    let flight = Flight::from_input(&input, &codec, rows).map_err(|e| e.to_string())?;
+    for diagnostic in &flight.diagnostics {
+        eprintln!("{}:{}", input.path(), diagnostic);
+    }
+    if args.check {
+        println!("passes: {}, issues: {}", flight.passes.len(), flight.diagnostics.len());
+    }
+
+    if args.map {
+        let aisles = if args.aisles.is_empty() { vec![codec.cols() / 2] } else { args.aisles.clone() };
+        print!("{}", flight.render(&aisles, color::enabled()));
+    }
+
+    if args.stats {
+        print_stats(&flight.stats());
+    }
+
+    if args.gaps {
+        let gaps = day05::find_gaps(&flight.taken_ids());
+        for id in &gaps {
+            let seat = codec.seat(*id);
+            println!("free seat: row {}, column {}, id {}", seat.row(), seat.col(), id);
+        }
+        match gaps.len() {
+            0 => println!("no free seat is between two taken ones"),
+            1 => {},
+            n => println!("ambiguous: {} candidate seats", n),
+        }
+    }
+
+    if args.strict && !flight.diagnostics.is_empty() {
+        return Err(format!("found {} duplicate, conflicting or out of range passes",
+            flight.diagnostics.len()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use clap::Args;
+
+use day06::query::{self, Masks, Query};
+
+#[derive(Args)]
+pub struct Day06Args {
+    /// Input file, '-' for stdin, defaults to day06/input.txt.
+    #[arg(long)]
+    pub input: Option<String>,
+    /// Query to count the matching questions of, summed over the groups,
+    /// e.g. "atleast(2) - [xyz]". Defaults to "any" and "all".
+    #[arg(long = "query")]
+    pub queries: Vec<Query>,
+    /// Also lists the questions matching each query in every group.
+    #[arg(long)]
+    pub groups: bool,
+}
+
+pub fn run(args: &Day06Args) -> Result<(), String> {
+    let input = super::load_input(6, args.input.as_deref())?;
+    let groups: Vec<Masks> = day06::parse_groups(&input)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(Masks::from_group)
+        .collect();
+
+    let queries = if args.queries.is_empty() {
+        vec![Query::any(), Query::all()]
+    } else {
+        args.queries.clone()
+    };
+
+    for query in &queries {
+        println!("{}: {}", query, query.count(&groups));
+        if args.groups {
+            for (i, masks) in groups.iter().enumerate() {
+                println!("  group {}: {}", i + 1, query::questions(query.eval(masks)));
+            }
+        }
+    }
+
+    Ok(())
+}
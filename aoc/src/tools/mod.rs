@@ -0,0 +1,35 @@
+//! Commands exposing what a day can do beyond solving its two parts.
+
+use common::input;
+use common::Input;
+
+use std::io::BufRead;
+
+pub mod day01;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+
+/// Returns |input|, or the path of the default input of the day
+/// numbered |number|.
+fn input_path(number: u8, input: Option<&str>) -> Result<String, String> {
+    match input {
+        Some(path) => Ok(path.to_string()),
+        None => aoc::find_day(number)
+            .map(|day| day.input_path())
+            .ok_or_else(|| format!("day {} is not solved", number)),
+    }
+}
+
+/// Loads |input|, or the default input of the day numbered |number|.
+pub fn load_input(number: u8, input: Option<&str>) -> Result<Input, String> {
+    Input::load(&input_path(number, input)?).map_err(|e| e.to_string())
+}
+
+/// Opens |input|, or the default input of the day numbered |number|,
+/// for streaming. Returns the name under which it is reported, along
+/// with its reader.
+pub fn open_input(number: u8, input: Option<&str>) -> Result<(String, Box<dyn BufRead>), String> {
+    input::open(&input_path(number, input)?).map_err(|e| e.to_string())
+}
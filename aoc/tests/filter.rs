@@ -0,0 +1,56 @@
+//! Tests of the passports written by `aoc day04 --filter`.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const BATCH: &str = "\
+pid:000000001 ecl:brn zzz:1 hcl:#123abc
+eyr:2025 byr:2002 hgt:180cm iyr:2015
+
+ecl:wat iyr:2015 eyr:2025 hgt:180cm hcl:#123abc
+cid:99 pid:1 byr:1990
+
+byr:x iyr:2015
+
+iyr:2015
+";
+
+const VALID: &str = "byr:2002 iyr:2015 eyr:2025 hgt:180cm hcl:#123abc ecl:brn pid:000000001 zzz:1\n\n";
+const INVALID: &str = "byr:1990 iyr:2015 eyr:2025 hgt:180cm hcl:#123abc ecl:wat pid:1 cid:99\n\n";
+const INCOMPLETE: &str = "iyr:2015\n\n";
+
+/// Runs `aoc day04 --filter |filter|` on |BATCH|, and returns its
+/// output.
+fn filter(filter: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["day04", "--input", "-", "--filter", filter])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(BATCH.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn valid() {
+    assert_eq!(filter("valid"), VALID);
+}
+
+#[test]
+fn invalid() {
+    // The skipped record is neither valid nor invalid.
+    assert_eq!(filter("invalid"), format!("{}{}", INVALID, INCOMPLETE));
+}
+
+#[test]
+fn complete() {
+    assert_eq!(filter("complete"), format!("{}{}", VALID, INVALID));
+}
+
+#[test]
+fn incomplete() {
+    assert_eq!(filter("incomplete"), INCOMPLETE);
+}
@@ -0,0 +1,41 @@
+//! Tests of the checks of `aoc day05` on the boarding passes.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const PASSES: &str = "FBFBBFFRLL\nFBFBBFFRLL\nFBFBBFFRRL\nFBFBBFFRRL\n";
+
+/// Runs aoc with |args| on |PASSES|.
+fn run(args: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(PASSES.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn lenient_by_default() {
+    let output = run(&["day05", "--input", "-", "--check"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "passes: 4, issues: 2\n");
+
+    let output = run(&["run", "5", "--input", "-"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "day 05: part 1 = 358, part 2 = 357\n");
+}
+
+#[test]
+fn strict_reports_every_issue() {
+    let output = run(&["day05", "--input", "-", "--strict"]);
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "\
+<stdin>:2: duplicate pass, first scanned on line 1 (found 'FBFBBFFRLL')
+<stdin>:4: duplicate pass, first scanned on line 3 (found 'FBFBBFFRRL')
+error: found 2 duplicate, conflicting or out of range passes
+");
+}
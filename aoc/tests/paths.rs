@@ -0,0 +1,34 @@
+//! Tests of the default inputs, which are found whatever the working
+//! directory.
+
+use std::env;
+use std::process::{Command, Output};
+
+/// Runs the runner with |args| from the temporary directory.
+fn run_elsewhere(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(env::temp_dir())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn default_input() {
+    let output = run_elsewhere(&["run", "1"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "day 01: part 1 = 989824, part 2 = 66432240\n");
+}
+
+#[test]
+fn default_answers() {
+    let output = run_elsewhere(&["verify"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with(", 0 failed, 0 skipped\n"));
+}
+
+#[test]
+fn tool_default_input() {
+    let output = run_elsewhere(&["day05", "--check"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,77 @@
+//! Regression tests over the sample inputs of the puzzles.
+//!
+//! Every dayNN/input-sample*.txt file is solved, and its answers are
+//! checked against the ones declared in the matching *.answers.toml
+//! file, e.g. input-sample-01.answers.toml for input-sample-01.txt.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc::answers::{self, Check, ExpectedAnswers};
+use aoc::{Day, DAYS};
+use common::Input;
+
+/// Returns the sample inputs of |day|, sorted by name.
+fn find_samples(day: &Day) -> Vec<PathBuf> {
+    let dir = aoc::workspace_root().join(day.dir());
+
+    let mut samples: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("could not list {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with("input-sample") && name.ends_with(".txt")
+        })
+        .collect();
+    samples.sort();
+
+    samples
+}
+
+/// Solves |sample| and checks its answers, returning the failures.
+fn check_sample(day: &Day, sample: &Path) -> Vec<String> {
+    let name = sample.display().to_string();
+    let answers_path = sample.with_extension("answers.toml");
+
+    let expected = match ExpectedAnswers::load(&answers_path.to_string_lossy()) {
+        Ok(Some(expected)) => expected,
+        Ok(None) => return vec![format!("{}: missing {}", name, answers_path.display())],
+        Err(e) => return vec![e.to_string()],
+    };
+
+    let answers = match Input::load(&name).and_then(|input| (day.solve)(&input)) {
+        Ok(answers) => answers,
+        Err(e) => return vec![format!("{}: {}", name, e)],
+    };
+
+    let parts = [
+        ("part 1", expected.part1.as_ref(), &answers.part1),
+        ("part 2", expected.part2.as_ref(), &answers.part2),
+    ];
+
+    let mut failures = Vec::new();
+    for (part, expected, answer) in parts.iter() {
+        if let Check::Fail(expected) = answers::check(*expected, answer) {
+            failures.push(format!("{}: {}: got {}, expected {}",
+                name, part, answer, expected));
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn samples() {
+    let mut num_samples = 0;
+    let mut failures = Vec::new();
+
+    for day in DAYS {
+        for sample in find_samples(day) {
+            num_samples += 1;
+            failures.extend(check_sample(day, &sample));
+        }
+    }
+
+    assert!(num_samples > 0, "no sample input found");
+    assert!(failures.is_empty(), "sample failures:\n{}", failures.join("\n"));
+}
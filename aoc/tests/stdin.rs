@@ -0,0 +1,46 @@
+//! Tests of inputs read from the standard input, given as "-".
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const SAMPLE: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+/// Runs the solver of day 1 on |stdin|.
+fn run_stdin(stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "1", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn plain() {
+    let output = run_stdin(SAMPLE.as_bytes());
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "day 01: part 1 = 514579, part 2 = 241861950\n");
+}
+
+#[test]
+fn gzip() {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(SAMPLE.as_bytes()).unwrap();
+    let output = run_stdin(&encoder.finish().unwrap());
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "day 01: part 1 = 514579, part 2 = 241861950\n");
+}
+
+#[test]
+fn errors_name_stdin() {
+    let output = run_stdin(b"1721\nx\n");
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr),
+        "error: day 01: <stdin>:2:1: invalid value: invalid digit found in string (found 'x')\n");
+}
@@ -0,0 +1,115 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+/// A position within an input, lines and columns being 1-based.
+///
+/// A line of 0 means the position has not been located within an
+/// input yet, see Error::at_line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Location {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "column {}", self.column)
+        } else {
+            write!(f, "{}:{}:{}", self.path, self.line, self.column)
+        }
+    }
+}
+
+/// The errors reported by the solutions.
+#[derive(Debug)]
+pub enum Error {
+    /// An input could not be read.
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    /// A part of an input could not be parsed.
+    Parse {
+        location: Location,
+        text: String,
+        message: String,
+    },
+    /// A row of a grid does not have the width of the first row.
+    RaggedRow {
+        location: Location,
+        expected: usize,
+        found: usize,
+    },
+    /// The input is well-formed, but the puzzle has no solution.
+    Unsolvable(String),
+    /// The input or the requested configuration is beyond what the
+    /// solver supports, e.g. it would exceed its memory budget.
+    Unsupported(String),
+}
+
+impl Error {
+    /// Creates a parse error on |text|, found at |column| of a line
+    /// yet to be located.
+    pub fn parse(column: usize, text: impl Into<String>, message: impl fmt::Display) -> Error {
+        Error::Parse {
+            location: Location {
+                column,
+                ..Location::default()
+            },
+            text: text.into(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Locates a parse error at |line| of |path|. Errors already
+    /// located are left untouched.
+    pub fn at_line(self, path: &str, line: usize) -> Error {
+        match self {
+            Error::Parse { location, text, message } if location.line == 0 =>
+                Error::Parse {
+                    location: Location {
+                        path: path.to_string(),
+                        line,
+                        column: location.column,
+                    },
+                    text,
+                    message,
+                },
+            _ =>
+                self,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } =>
+                write!(f, "{}: {}", path, source),
+            Error::Parse { location, text, message } if text.is_empty() =>
+                write!(f, "{}: {}", location, message),
+            Error::Parse { location, text, message } =>
+                write!(f, "{}: {} (found '{}')", location, message, text),
+            Error::RaggedRow { location, expected, found } =>
+                write!(f, "{}: row has width {}, expected {}", location, found, expected),
+            Error::Unsolvable(message) =>
+                write!(f, "no solution: {}", message),
+            Error::Unsupported(message) =>
+                write!(f, "unsupported: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
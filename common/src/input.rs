@@ -0,0 +1,580 @@
+//! Loading and splitting of puzzle inputs.
+//!
+//! Inputs are read from a file, or from stdin when the path is "-", and
+//! are transparently decompressed when gzip-compressed. Every helper
+//! reports the file and line at fault instead of panicking.
+//!
+//! Inputs are usually loaded whole, but inputs made of records can also
+//! be streamed one record at a time, see RecordReader.
+
+use flate2::read::GzDecoder;
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+
+use crate::{Error, Location, Result};
+
+/// Path designating the standard input.
+pub const STDIN_PATH: &str = "-";
+
+/// Name under which the standard input is reported.
+const STDIN_NAME: &str = "<stdin>";
+
+/// Magic bytes at the beginning of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A line of an input, along with its 1-based number.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// A group of consecutive non-blank lines.
+#[derive(Clone, Debug)]
+pub struct Record<'a> {
+    pub lines: Vec<Line<'a>>,
+}
+
+impl<'a> Record<'a> {
+    /// Returns the number of the first line of the record.
+    pub fn first_line(&self) -> usize {
+        self.lines[0].number
+    }
+
+    /// Returns the number of the last line of the record.
+    pub fn last_line(&self) -> usize {
+        self.lines[self.lines.len() - 1].number
+    }
+
+    /// Returns the text of each line of the record.
+    pub fn texts(&self) -> Vec<&'a str> {
+        self.lines.iter().map(|line| line.text).collect()
+    }
+}
+
+/// A record read from a stream, owning its lines.
+#[derive(Clone, Debug, Default)]
+pub struct RecordBuf {
+    pub first_line: usize,
+    pub texts: Vec<String>,
+}
+
+impl RecordBuf {
+    /// Borrows the record as a Record.
+    pub fn record(&self) -> Record<'_> {
+        Record {
+            lines: self.texts.iter()
+                .enumerate()
+                .map(|(i, text)| Line { number: self.first_line + i, text })
+                .collect(),
+        }
+    }
+}
+
+/// Iterator over the records of a stream, separated by blank lines.
+/// Only the record being read is held in memory.
+pub struct RecordReader<R> {
+    path: String,
+    reader: R,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    /// Reads records from |reader|, |path| being only used to report
+    /// errors.
+    pub fn new(path: &str, reader: R) -> RecordReader<R> {
+        RecordReader {
+            path: path.to_string(),
+            reader,
+            line: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for RecordReader<R> {
+    type Item = Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Result<RecordBuf>> {
+        let mut record = RecordBuf::default();
+
+        while !self.done {
+            let mut text = String::new();
+            match self.reader.read_line(&mut text) {
+                Ok(0) => {
+                    self.done = true;
+                    break;
+                },
+                Ok(_) => {
+                },
+                Err(source) => {
+                    self.done = true;
+                    return Some(Err(Error::Io {
+                        path: self.path.clone(),
+                        source,
+                    }));
+                },
+            }
+            self.line += 1;
+
+            let text = text.trim_end_matches(['\n', '\r']);
+            if !text.trim().is_empty() {
+                if record.texts.is_empty() {
+                    record.first_line = self.line;
+                }
+                record.texts.push(text.to_string());
+            } else if !record.texts.is_empty() {
+                break;
+            }
+        }
+
+        if record.texts.is_empty() {
+            None
+        } else {
+            Some(Ok(record))
+        }
+    }
+}
+
+/// Opens the input at |path| for streaming, "-" being the standard
+/// input, decompressing it on the fly when gzip-compressed. Returns the
+/// name under which the input is reported, along with its reader.
+pub fn open(path: &str) -> Result<(String, Box<dyn BufRead>)> {
+    let name = if path == STDIN_PATH { STDIN_NAME } else { path };
+    let io_error = |source| Error::Io {
+        path: name.to_string(),
+        source,
+    };
+
+    let source: Box<dyn Read> = if path == STDIN_PATH {
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(path).map_err(io_error)?)
+    };
+
+    let mut reader = BufReader::new(source);
+    let compressed = reader.fill_buf()
+        .map_err(io_error)?
+        .starts_with(&GZIP_MAGIC);
+    let reader: Box<dyn BufRead> = if compressed {
+        Box::new(BufReader::new(GzDecoder::new(reader)))
+    } else {
+        Box::new(reader)
+    };
+
+    Ok((name.to_string(), reader))
+}
+
+/// A rectangular grid of characters, stored row by row.
+#[derive(Clone, Debug)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<u8>,
+}
+
+impl Grid {
+    /// Returns the (x, y)-th cell.
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.cells[y * self.width + x]
+    }
+
+    /// Returns the |y|-th row.
+    pub fn row(&self, y: usize) -> &[u8] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+}
+
+/// The content of a puzzle input.
+#[derive(Clone, Debug)]
+pub struct Input {
+    path: String,
+    text: String,
+}
+
+impl Input {
+    /// Loads the input at |path|, "-" being the standard input.
+    pub fn load(path: &str) -> Result<Input> {
+        let (name, mut reader) = open(path)?;
+
+        let mut raw = Vec::new();
+        reader.read_to_end(&mut raw)
+            .map_err(|source| Error::Io {
+                path: name.clone(),
+                source,
+            })?;
+
+        let text = String::from_utf8(raw)
+            .map_err(|e| Error::Io {
+                path: name.clone(),
+                source: io::Error::new(io::ErrorKind::InvalidData, e),
+            })?;
+
+        Ok(Input::from_string(&name, text))
+    }
+
+    /// Creates an input from its content, |path| being only used
+    /// to report errors.
+    pub fn from_string(path: &str, text: String) -> Input {
+        Input {
+            path: path.to_string(),
+            text,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Creates a parse error on |text|, found at |line| and |column|
+    /// of the input.
+    pub fn error(&self, line: usize, column: usize, text: impl Into<String>,
+                 message: impl fmt::Display) -> Error {
+        Error::parse(column, text, message).at_line(&self.path, line)
+    }
+
+    /// Creates a parse error at byte |offset| of the input, as reported
+    /// by parsers which do not count lines, the text found being the
+    /// whole line.
+    pub fn error_at_offset(&self, offset: usize, message: impl fmt::Display) -> Error {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |ind| ind + 1) + 1;
+        let found = self.text.lines().nth(line - 1).unwrap_or("").trim();
+        self.error(line, column, found, message)
+    }
+
+    /// Locates |err| at |line| of the input.
+    pub fn locate(&self, err: Error, line: usize) -> Error {
+        err.at_line(&self.path, line)
+    }
+
+    /// Returns every line of the input, without its end-of-line.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.text.lines()
+            .enumerate()
+            .map(|(i, text)| Line { number: i + 1, text })
+    }
+
+    /// Parses every non-blank line of the input as a |T|.
+    pub fn parse_lines<T>(&self) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let mut res = Vec::new();
+
+        for line in self.lines() {
+            if line.text.trim().is_empty() {
+                continue;
+            }
+
+            let text = line.text.trim();
+            let column = line.text.find(text).unwrap_or(0) + 1;
+            let item = text.parse::<T>()
+                .map_err(|e| self.error(line.number, column, text,
+                    format!("invalid value: {}", e)))?;
+            res.push(item);
+        }
+
+        Ok(res)
+    }
+
+    /// Returns the records of the input, separated by blank lines.
+    pub fn records(&self) -> Vec<Record<'_>> {
+        let mut records = Vec::new();
+        let mut lines = Vec::new();
+
+        for line in self.lines() {
+            if line.text.trim().is_empty() {
+                if !lines.is_empty() {
+                    records.push(Record { lines });
+                    lines = Vec::new();
+                }
+            } else {
+                lines.push(line);
+            }
+        }
+
+        if !lines.is_empty() {
+            records.push(Record { lines });
+        }
+
+        records
+    }
+
+    /// Parses the input as a grid of characters. CRLF line endings and
+    /// trailing blank lines are ignored, but every row must have the
+    /// same width.
+    pub fn grid(&self) -> Result<Grid> {
+        let rows: Vec<Line> = self.lines().collect();
+        let num_rows = rows.iter()
+            .rposition(|line| !line.text.is_empty())
+            .map_or(0, |pos| pos + 1);
+        if num_rows == 0 {
+            return Err(self.error(1, 1, "", "grid is empty"));
+        }
+
+        let width = rows[0].text.len();
+        let mut cells = Vec::with_capacity(width * num_rows);
+
+        for line in &rows[..num_rows] {
+            if line.text.len() != width {
+                return Err(Error::RaggedRow {
+                    location: Location {
+                        path: self.path.clone(),
+                        line: line.number,
+                        column: line.text.len().min(width) + 1,
+                    },
+                    expected: width,
+                    found: line.text.len(),
+                });
+            }
+            cells.extend_from_slice(line.text.as_bytes());
+        }
+
+        Ok(Grid {
+            width,
+            height: num_rows,
+            cells,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    /// Writes |bytes| to a temporary file named after |name|, and
+    /// returns its path.
+    fn temp_file(name: &str, bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("common-input-{}-{}", std::process::id(), name));
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn load_plain() {
+        let path = temp_file("plain.txt", b"1\n2\n");
+        let input = Input::load(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(input.path(), path.to_str().unwrap());
+        assert_eq!(input.text(), "1\n2\n");
+    }
+
+    #[test]
+    fn load_gzip() {
+        // Compressed inputs are detected by their content, not their name.
+        let path = temp_file("compressed.txt", &gzip("1\n2\n"));
+        let input = Input::load(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(input.text(), "1\n2\n");
+        assert_eq!(input.parse_lines::<u32>().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn load_errors() {
+        let missing = std::env::temp_dir().join("common-input-missing.txt");
+        match Input::load(missing.to_str().unwrap()) {
+            Err(Error::Io { path, source }) =>
+                assert_eq!((path.as_str(), source.kind()), (missing.to_str().unwrap(), io::ErrorKind::NotFound)),
+            res =>
+                panic!("{:?}", res),
+        }
+
+        let path = temp_file("binary.txt", &[b'1', 0xff, b'\n']);
+        let res = Input::load(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        match res {
+            Err(Error::Io { source, .. }) =>
+                assert_eq!(source.kind(), io::ErrorKind::InvalidData),
+            res =>
+                panic!("{:?}", res),
+        }
+    }
+
+    #[test]
+    fn parse_lines() {
+        let input = Input::from_string("numbers", "1\n\n  -2 \n3\n".to_string());
+        assert_eq!(input.parse_lines::<i32>().unwrap(), vec![1, -2, 3]);
+
+        let input = Input::from_string("numbers", "1\n\n  4x \n".to_string());
+        assert_eq!(input.parse_lines::<i32>().unwrap_err().to_string(),
+            "numbers:3:3: invalid value: invalid digit found in string (found '4x')");
+        match input.parse_lines::<i32>() {
+            Err(Error::Parse { location, text, .. }) =>
+                assert_eq!((location.path.as_str(), location.line, location.column, text.as_str()), ("numbers", 3, 3, "4x")),
+            res =>
+                panic!("{:?}", res),
+        }
+    }
+
+    #[test]
+    fn error_at_offset() {
+        let input = Input::from_string("a.toml", "a = 1\nb = x\n".to_string());
+        assert_eq!(input.error_at_offset(0, "oops").to_string(), "a.toml:1:1: oops (found 'a = 1')");
+        assert_eq!(input.error_at_offset(10, "oops").to_string(), "a.toml:2:5: oops (found 'b = x')");
+        assert_eq!(input.error_at_offset(12, "oops").to_string(), "a.toml:3:1: oops");
+        assert_eq!(input.error_at_offset(100, "oops").to_string(), "a.toml:3:1: oops");
+    }
+
+    #[test]
+    fn records() {
+        let input = Input::from_string("records", "\na b\nc\n  \n\nd\n".to_string());
+        let records = input.records();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].texts(), vec!["a b", "c"]);
+        assert_eq!((records[0].first_line(), records[0].last_line()), (2, 3));
+        assert_eq!(records[1].texts(), vec!["d"]);
+        assert_eq!((records[1].first_line(), records[1].last_line()), (6, 6));
+        assert!(Input::from_string("records", "\n \n".to_string()).records().is_empty());
+    }
+
+    #[test]
+    fn grid() {
+        let grid = Input::from_string("grid", "ab\ncd\nef\n".to_string()).grid().unwrap();
+        assert_eq!((grid.width, grid.height), (2, 3));
+        assert_eq!(grid.cells, b"abcdef");
+        assert_eq!(grid.row(1), b"cd");
+        assert_eq!(grid.get(1, 2), b'f');
+
+        let error = Input::from_string("grid", "\n\n".to_string()).grid().unwrap_err();
+        assert_eq!(error.to_string(), "grid:1:1: grid is empty");
+    }
+
+    #[test]
+    fn grid_crlf() {
+        let grid = Input::from_string("grid", "ab\r\ncd\r\n".to_string()).grid().unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.cells, b"abcd");
+    }
+
+    #[test]
+    fn grid_trailing_newlines() {
+        let grid = Input::from_string("grid", "ab\ncd\n\n\n".to_string()).grid().unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+
+        let grid = Input::from_string("grid", "ab\ncd".to_string()).grid().unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+    }
+
+    #[test]
+    fn grid_ragged_rows() {
+        let error = Input::from_string("grid", "abc\nde\nfgh\n".to_string()).grid().unwrap_err();
+        match &error {
+            Error::RaggedRow { location, expected, found } =>
+                assert_eq!((location.line, location.column, *expected, *found), (2, 3, 3, 2)),
+            _ =>
+                panic!("{:?}", error),
+        }
+
+        // Blank lines within the grid are rows too.
+        let error = Input::from_string("grid", "abc\n\nfgh\n".to_string()).grid().unwrap_err();
+        assert!(matches!(error, Error::RaggedRow { found: 0, .. }), "{:?}", error);
+
+        let error = Input::from_string("grid", "abc\nabcd\n".to_string()).grid().unwrap_err();
+        assert!(matches!(error, Error::RaggedRow { expected: 3, found: 4, .. }), "{:?}", error);
+    }
+
+    /// Returns the first line and the texts of every record of |text|.
+    fn read_records(text: &str) -> Vec<(usize, Vec<String>)> {
+        RecordReader::new("records", io::Cursor::new(text.as_bytes()))
+            .map(|res| res.map(|buf| (buf.first_line, buf.texts)).unwrap())
+            .collect()
+    }
+
+    fn record(first_line: usize, texts: &[&str]) -> (usize, Vec<String>) {
+        (first_line, texts.iter().map(|text| text.to_string()).collect())
+    }
+
+    /// A reader failing after returning |text|.
+    struct Failing<'a> {
+        text: &'a [u8],
+    }
+
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.text.is_empty() {
+                return Err(io::Error::other("disk on fire"));
+            }
+            let len = self.text.len().min(buf.len());
+            buf[..len].copy_from_slice(&self.text[..len]);
+            self.text = &self.text[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn record_reader_splits_on_blank_lines() {
+        assert_eq!(read_records("a b\nc\n\nd\n"), vec![
+            record(1, &["a b", "c"]),
+            record(4, &["d"]),
+        ]);
+        // Leading, repeated and whitespace-only blank lines.
+        assert_eq!(read_records("\n\na\n \t\n\n\nb\nc"), vec![
+            record(3, &["a"]),
+            record(7, &["b", "c"]),
+        ]);
+        assert_eq!(read_records(""), vec![]);
+        assert_eq!(read_records("\n  \n"), vec![]);
+    }
+
+    #[test]
+    fn record_reader_crlf() {
+        assert_eq!(read_records("a\r\nb \r\n\r\nc\r\n"), vec![
+            record(1, &["a", "b "]),
+            record(4, &["c"]),
+        ]);
+    }
+
+    #[test]
+    fn record_reader_matches_records() {
+        let text = "x\n\n\ny z\nw\n\n  \nv\n";
+        let input = Input::from_string("records", text.to_string());
+        let whole: Vec<(usize, Vec<String>)> = input.records().iter()
+            .map(|record| (record.first_line(), record.texts().iter().map(|text| text.to_string()).collect()))
+            .collect();
+        assert_eq!(read_records(text), whole);
+
+        let buf = RecordBuf {
+            first_line: 4,
+            texts: vec!["y z".to_string(), "w".to_string()],
+        };
+        let record = buf.record();
+        assert_eq!((record.first_line(), record.last_line()), (4, 5));
+        assert_eq!(record.texts(), vec!["y z", "w"]);
+    }
+
+    #[test]
+    fn record_reader_errors() {
+        let reader = BufReader::new(Failing { text: b"a\n\nb\n" });
+        let mut records = RecordReader::new("records", reader);
+        assert_eq!(records.next().unwrap().unwrap().texts, vec!["a"]);
+        assert_eq!(records.next().unwrap().unwrap_err().to_string(), "records: disk on fire");
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,9 @@
+//! Building blocks shared by the solutions of every day.
+
+pub mod error;
+pub mod input;
+pub mod solution;
+
+pub use error::{Error, Location, Result};
+pub use input::Input;
+pub use solution::{solve, Answer, Answers, Solution};
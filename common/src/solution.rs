@@ -0,0 +1,112 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::{Input, Result};
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// The part has not been solved yet.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(num: $t) -> Answer {
+                    Answer::Int(num.into())
+                }
+            }
+        )*
+    };
+}
+
+/// Answers of types wider than i64 fall back to text when they do not
+/// fit.
+macro_rules! impl_answer_from_wide_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(num: $t) -> Answer {
+                    i64::try_from(num).map_or_else(|_| Answer::Text(num.to_string()), Answer::Int)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(i32, i64, u32);
+impl_answer_from_wide_int!(u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// The answers to both parts of a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answers {
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+/// A solution to the puzzle of one day.
+///
+/// The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    /// Parses the puzzle input.
+    fn parse(input: &Input) -> Result<Self::Input>;
+
+    /// Solves the first part of the puzzle.
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    /// Solves the second part of the puzzle.
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// Parses |input| and solves both parts of the puzzle with |S|.
+pub fn solve<S: Solution>(input: &Input) -> Result<Answers> {
+    let parsed = S::parse(input)?;
+
+    Ok(Answers {
+        part1: S::part1(&parsed)?,
+        part2: S::part2(&parsed)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_int() {
+        assert_eq!(Answer::from(-3i32), Answer::Int(-3));
+        assert_eq!(Answer::from(u32::MAX), Answer::Int(4_294_967_295));
+        assert_eq!(Answer::from(i64::MAX as u64), Answer::Int(i64::MAX));
+        assert_eq!(Answer::from(i64::MAX as u64 + 1), Answer::Text("9223372036854775808".to_string()));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+    }
+}
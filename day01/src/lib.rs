@@ -0,0 +1,385 @@
+use common::{Answer, Error, Input, Result, Solution};
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+pub mod subset_sum;
+
+/// The sum the expense report entries must add up to.
+pub const TARGET: i64 = 2020;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &Input) -> Result<Self::Input> {
+        input.parse_lines::<i64>()
+    }
+
+    fn part1(nums: &Self::Input) -> Result<Answer> {
+        solve_product(TARGET, 2, nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<Answer> {
+        solve_product(TARGET, 3, nums)
+    }
+}
+
+/// Returns the product of |nums|, unless it overflows.
+pub fn product(nums: &[i64]) -> Option<i64> {
+    nums.iter().try_fold(1i64, |acc, num| acc.checked_mul(*num))
+}
+
+/// Finds |k| entries summing to |target| and returns their product.
+fn solve_product(target: i64, k: usize, nums: &[i64]) -> Result<Answer> {
+    let combination = solve_k(target, k, nums)
+        .ok_or_else(|| Error::Unsolvable(
+            format!("no {} entries sum to {}", k, target)))?;
+    let res = product(&combination)
+        .ok_or_else(|| Error::Unsolvable("product overflows".to_string()))?;
+
+    Ok(res.into())
+}
+
+/// Finds |k| entries of |nums| summing to |target|, and returns them in
+/// ascending order. An entry can be used as many times as it appears
+/// in |nums|.
+pub fn solve_k(target: i64, k: usize, nums: &[i64]) -> Option<Vec<i64>> {
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+
+    solve_k_sorted(target as i128, k, &sorted)
+}
+
+/// Returns the sum of |nums|, which cannot overflow.
+fn wide_sum(nums: &[i64]) -> i128 {
+    nums.iter().map(|num| *num as i128).sum()
+}
+
+// Sums and targets are tracked as i128, since sums of entries, and the
+// targets left once some entries are picked, can be beyond i64.
+fn solve_k_sorted(target: i128, k: usize, nums: &[i64]) -> Option<Vec<i64>> {
+    if k > nums.len() {
+        return None;
+    }
+
+    match k {
+        0 if target == 0 =>
+            Some(Vec::new()),
+        0 =>
+            None,
+        1 =>
+            i64::try_from(target).ok()
+                .filter(|target| nums.binary_search(target).is_ok())
+                .map(|target| vec![target]),
+        2 =>
+            solve_two_sorted(target, nums).map(|(x, y)| vec![x, y]),
+        _ => {
+            // Reduce the problem to a (k - 1)-sum over the entries
+            // following each candidate. Since the entries are sorted,
+            // we can stop as soon as the smallest sum reachable from a
+            // candidate is above the target.
+            for i in 0..=(nums.len() - k) {
+                if i > 0 && nums[i] == nums[i - 1] {
+                    continue;
+                }
+
+                let min_sum = wide_sum(&nums[i..i + k]);
+                if min_sum > target {
+                    break;
+                }
+
+                let max_sum = nums[i] as i128 + wide_sum(&nums[nums.len() - k + 1..]);
+                if max_sum < target {
+                    continue;
+                }
+
+                if let Some(mut res) = solve_k_sorted(target - nums[i] as i128, k - 1, &nums[i + 1..]) {
+                    res.insert(0, nums[i]);
+                    return Some(res);
+                }
+            }
+
+            None
+        },
+    }
+}
+
+/// Finds a pair of entries of the sorted |nums| summing to |target|,
+/// by moving two pointers towards each other.
+fn solve_two_sorted(target: i128, nums: &[i64]) -> Option<(i64, i64)> {
+    if nums.len() < 2 {
+        return None;
+    }
+
+    let mut lo = 0;
+    let mut hi = nums.len() - 1;
+
+    while lo < hi {
+        match (nums[lo] as i128 + nums[hi] as i128).cmp(&target) {
+            Ordering::Less => lo += 1,
+            Ordering::Greater => hi -= 1,
+            Ordering::Equal => return Some((nums[lo], nums[hi])),
+        }
+    }
+
+    None
+}
+
+/// Iterates over every distinct combination of |k| entries summing to a
+/// target, see combinations().
+pub struct Combinations {
+    nums: Vec<i64>,
+    k: usize,
+    target: i128,
+    /// Indices of the entries picked so far, and their sum.
+    stack: Vec<usize>,
+    sum: i128,
+    /// Index of the next candidate at the current depth.
+    cursor: usize,
+    done: bool,
+}
+
+/// Returns an iterator over every distinct combination of |k| entries
+/// of |nums| summing to |target|. Each combination is yielded once, in
+/// ascending order, and combinations are yielded in lexicographic
+/// order.
+pub fn combinations(target: i64, k: usize, nums: &[i64]) -> Combinations {
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+
+    Combinations {
+        done: k > sorted.len(),
+        nums: sorted,
+        k,
+        target: target as i128,
+        stack: Vec::with_capacity(k),
+        sum: 0,
+        cursor: 0,
+    }
+}
+
+impl Combinations {
+    /// Returns the values of the entries picked so far, along with |last|.
+    fn combination(&self, last: i64) -> Vec<i64> {
+        let mut res: Vec<i64> = self.stack.iter().map(|i| self.nums[*i]).collect();
+        res.push(last);
+        res
+    }
+
+    /// Finds the next candidate at the current depth, skipping the
+    /// duplicates and the candidates that cannot reach the target.
+    fn next_candidate(&mut self) -> Option<usize> {
+        let n = self.nums.len();
+        let remaining = self.k - self.stack.len();
+        let depth_start = self.stack.last().map_or(0, |i| i + 1);
+
+        while self.cursor + remaining <= n {
+            let i = self.cursor;
+            self.cursor += 1;
+
+            if i > depth_start && self.nums[i] == self.nums[i - 1] {
+                continue;
+            }
+
+            let min_sum = self.sum + wide_sum(&self.nums[i..i + remaining]);
+            if min_sum > self.target {
+                break;
+            }
+
+            let max_sum = self.sum + self.nums[i] as i128
+                + wide_sum(&self.nums[n - remaining + 1..]);
+            if max_sum < self.target {
+                continue;
+            }
+
+            return Some(i);
+        }
+
+        None
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = Vec<i64>;
+
+    fn next(&mut self) -> Option<Vec<i64>> {
+        if self.done {
+            return None;
+        }
+
+        if self.k == 0 {
+            self.done = true;
+            return if self.target == 0 { Some(Vec::new()) } else { None };
+        }
+
+        loop {
+            if self.stack.len() + 1 == self.k {
+                // A single entry is left to pick, and it can only have
+                // one value: look it up and backtrack.
+                let depth_start = self.stack.last().map_or(0, |i| i + 1);
+                let found = i64::try_from(self.target - self.sum).ok()
+                    .filter(|last| self.cursor == depth_start
+                        && self.nums[depth_start..].binary_search(last).is_ok());
+                self.cursor = self.nums.len();
+                if let Some(last) = found {
+                    return Some(self.combination(last));
+                }
+            } else if let Some(i) = self.next_candidate() {
+                self.stack.push(i);
+                self.sum += self.nums[i] as i128;
+                self.cursor = i + 1;
+                continue;
+            }
+
+            match self.stack.pop() {
+                Some(i) => {
+                    self.sum -= self.nums[i] as i128;
+                    self.cursor = i + 1;
+                },
+                None => {
+                    self.done = true;
+                    return None;
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [i64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn solve_k_example() {
+        assert_eq!(solve_k(TARGET, 2, &EXAMPLE), Some(vec![299, 1721]));
+        assert_eq!(solve_k(TARGET, 3, &EXAMPLE), Some(vec![366, 675, 979]));
+        assert_eq!(solve_k(TARGET, 4, &EXAMPLE), None);
+    }
+
+    #[test]
+    fn solve_k_duplicates() {
+        assert_eq!(solve_k(2020, 2, &[1010]), None);
+        assert_eq!(solve_k(2020, 2, &[1010, 1010]), Some(vec![1010, 1010]));
+        assert_eq!(solve_k(3, 3, &[1, 1, 2]), None);
+        assert_eq!(solve_k(3, 3, &[1, 1, 1, 2]), Some(vec![1, 1, 1]));
+    }
+
+    #[test]
+    fn solve_k_zero() {
+        assert_eq!(solve_k(0, 0, &[]), Some(vec![]));
+        assert_eq!(solve_k(0, 0, &EXAMPLE), Some(vec![]));
+        assert_eq!(solve_k(TARGET, 0, &EXAMPLE), None);
+    }
+
+    #[test]
+    fn solve_k_negative() {
+        assert_eq!(solve_k(0, 2, &[-3, 1, 3]), Some(vec![-3, 3]));
+        assert_eq!(solve_k(-6, 3, &[5, -1, -2, -3]), Some(vec![-3, -2, -1]));
+        assert_eq!(solve_k(2, 3, &[4, -5, 3, 0]), Some(vec![-5, 3, 4]));
+        assert_eq!(solve_k(-1, 2, &[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn solve_k_overflow() {
+        let nums = [i64::MAX, 1, 2];
+        assert_eq!(solve_k(TARGET, 3, &nums), None);
+        assert_eq!(solve_k(-5, 2, &nums), None);
+        assert_eq!(solve_k(i64::MAX, 2, &[i64::MAX, 0, 1]), Some(vec![0, i64::MAX]));
+        assert_eq!(solve_k(i64::MIN, 3, &[i64::MIN, i64::MAX, i64::MAX, -1]), None);
+        assert_eq!(solve_k(-2, 3, &[i64::MIN, i64::MAX, i64::MAX, -1]), Some(vec![i64::MIN, -1, i64::MAX]));
+        assert_eq!(solve_k(0, 4, &[i64::MIN, i64::MIN, i64::MAX, i64::MAX, 2]), None);
+        assert_eq!(solve_k(-2, 4, &[i64::MIN, i64::MIN, i64::MAX, i64::MAX, 2]),
+            Some(vec![i64::MIN, i64::MIN, i64::MAX, i64::MAX]));
+    }
+
+    #[test]
+    fn solve_k_too_few_entries() {
+        assert_eq!(solve_k(1, 1, &[]), None);
+        assert_eq!(solve_k(6, 4, &[1, 2, 3]), None);
+    }
+
+    fn all(target: i64, k: usize, nums: &[i64]) -> Vec<Vec<i64>> {
+        combinations(target, k, nums).collect()
+    }
+
+    #[test]
+    fn combinations_example() {
+        assert_eq!(all(TARGET, 2, &EXAMPLE), vec![vec![299, 1721]]);
+        assert_eq!(all(TARGET, 3, &EXAMPLE), vec![vec![366, 675, 979]]);
+        assert_eq!(all(TARGET, 7, &EXAMPLE), Vec::<Vec<i64>>::new());
+    }
+
+    #[test]
+    fn combinations_ordered() {
+        assert_eq!(all(6, 2, &[5, 1, 4, 2, 3]), vec![vec![1, 5], vec![2, 4]]);
+        assert_eq!(all(6, 3, &[5, 1, 4, 2, 3, 0]), vec![
+            vec![0, 1, 5],
+            vec![0, 2, 4],
+            vec![1, 2, 3],
+        ]);
+    }
+
+    #[test]
+    fn combinations_duplicates() {
+        assert_eq!(all(4, 2, &[2, 2, 2, 1, 3, 3]), vec![vec![1, 3], vec![2, 2]]);
+        assert_eq!(all(4, 2, &[2, 1, 3]), vec![vec![1, 3]]);
+        assert_eq!(all(3, 3, &[1, 1, 1, 1]), vec![vec![1, 1, 1]]);
+    }
+
+    #[test]
+    fn combinations_zero() {
+        assert_eq!(all(0, 0, &[]), vec![Vec::<i64>::new()]);
+        assert_eq!(all(0, 0, &EXAMPLE), vec![Vec::<i64>::new()]);
+        assert_eq!(all(TARGET, 0, &EXAMPLE), Vec::<Vec<i64>>::new());
+    }
+
+    #[test]
+    fn combinations_negative() {
+        assert_eq!(all(0, 2, &[-3, 3, -1, 1, 0]), vec![vec![-3, 3], vec![-1, 1]]);
+        assert_eq!(all(0, 3, &[-2, -1, 0, 1, 2]), vec![
+            vec![-2, 0, 2],
+            vec![-1, 0, 1],
+        ]);
+        assert_eq!(all(-3, 2, &[-2, -1, -1, 0, -3]), vec![vec![-3, 0], vec![-2, -1]]);
+    }
+
+    #[test]
+    fn combinations_overflow() {
+        assert_eq!(all(TARGET, 2, &[i64::MAX, i64::MAX]), Vec::<Vec<i64>>::new());
+        assert_eq!(all(-2, 2, &[i64::MAX, i64::MAX]), Vec::<Vec<i64>>::new());
+        assert_eq!(all(TARGET, 3, &[i64::MAX, i64::MAX, 1, 2]), Vec::<Vec<i64>>::new());
+        assert_eq!(all(-1, 2, &[i64::MIN, i64::MAX, i64::MIN, 0, -1]), vec![
+            vec![i64::MIN, i64::MAX],
+            vec![-1, 0],
+        ]);
+        assert_eq!(all(-2, 4, &[i64::MIN, i64::MIN, i64::MAX, i64::MAX, 2]), vec![
+            vec![i64::MIN, i64::MIN, i64::MAX, i64::MAX],
+        ]);
+    }
+
+    #[test]
+    fn combinations_match_solve_k() {
+        let nums = [7, -4, 3, 3, 0, 12, -9, 5, 1, 8];
+        for k in 0..=nums.len() {
+            for target in -20..=40 {
+                let found = all(target, k, &nums);
+                assert_eq!(found.first().cloned(), solve_k(target, k, &nums), "{} {}", target, k);
+                let mut sorted = found.clone();
+                sorted.sort();
+                sorted.dedup();
+                assert_eq!(found, sorted);
+            }
+        }
+    }
+
+    #[test]
+    fn product_overflow() {
+        assert_eq!(product(&[]), Some(1));
+        assert_eq!(product(&[-3, 4]), Some(-12));
+        assert_eq!(product(&[i64::MAX, 2]), None);
+    }
+}
@@ -0,0 +1,295 @@
+//! Subset sums over the expense report, for subsets of any size.
+//!
+//! Sums are tracked with dynamic programming over every sum between 0
+//! and the target, which restricts the entries and the target to
+//! non-negative values.
+
+use common::{Error, Result};
+
+/// Default memory budget of exists() and solve(), in bytes.
+pub const DEFAULT_MEMORY_BUDGET: usize = 256 << 20;
+
+/// A fixed-size set of bits.
+#[derive(Clone, Debug)]
+struct Bitset {
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    fn new(num_bits: usize) -> Bitset {
+        Bitset {
+            words: vec![0; num_bits.div_ceil(64)],
+        }
+    }
+
+    /// Returns the number of bytes used by a set of |num_bits| bits.
+    fn size_of(num_bits: usize) -> usize {
+        num_bits.div_ceil(64) * 8
+    }
+
+    fn get(&self, i: usize) -> bool {
+        (self.words[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    fn set(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    /// Sets every bit i + |shift| for which bit i is set. Bits shifted
+    /// past the end of the set are dropped.
+    fn or_shifted(&mut self, shift: usize) {
+        let word_shift = shift / 64;
+        let bit_shift = shift % 64;
+
+        for i in (word_shift..self.words.len()).rev() {
+            let src = i - word_shift;
+            let mut word = self.words[src] << bit_shift;
+            if bit_shift > 0 && src > 0 {
+                word |= self.words[src - 1] >> (64 - bit_shift);
+            }
+            self.words[i] |= word;
+        }
+    }
+}
+
+/// Checks the entries and the target can be handled, and returns the
+/// target as an index into the sums.
+fn check_input(target: i64, nums: &[i64]) -> Result<usize> {
+    if target < 0 || nums.iter().any(|num| *num < 0) {
+        return Err(Error::Unsupported(
+            "subset sums require a non-negative target and entries".to_string()));
+    }
+
+    Ok(target as usize)
+}
+
+/// Fails if |needed| bytes exceed |memory_budget|.
+fn check_budget(needed: usize, memory_budget: usize) -> Result<()> {
+    if needed > memory_budget {
+        return Err(Error::Unsupported(format!(
+            "subset sums need {} bytes, the memory budget is {} bytes",
+            needed, memory_budget)));
+    }
+
+    Ok(())
+}
+
+/// Returns whether a subset of |nums| sums to |target|. Only needs one
+/// bit per sum, but fails rather than use more than |memory_budget|
+/// bytes.
+pub fn exists(target: i64, nums: &[i64], memory_budget: usize) -> Result<bool> {
+    let target = check_input(target, nums)?;
+    check_budget(Bitset::size_of(target.saturating_add(1)), memory_budget)?;
+
+    let mut reach = Bitset::new(target + 1);
+    reach.set(0);
+    for num in nums {
+        reach.or_shifted(*num as usize);
+    }
+
+    Ok(reach.get(target))
+}
+
+/// The subsets of the expense report summing to a target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// One of the subsets with the fewest entries, in ascending order.
+    pub smallest: Option<Vec<i64>>,
+    /// The number of subsets, each entry being distinct even when
+    /// values are duplicated. None if the count overflows.
+    pub count: Option<u128>,
+}
+
+/// Returns the number of bytes needed by solve().
+pub fn memory_needed(target: i64, nums: &[i64]) -> usize {
+    let num_sums = (target.max(0) as usize).saturating_add(1);
+    let num_items = nums.iter().filter(|num| **num <= target).count();
+
+    // The smallest subset and the count of each sum, along with the
+    // sums improved by each entry to reconstruct the smallest subset.
+    num_sums.saturating_mul(std::mem::size_of::<u32>() + std::mem::size_of::<u128>())
+        .saturating_add(num_items.saturating_mul(Bitset::size_of(num_sums)))
+}
+
+/// Finds the smallest subset of |nums| summing to |target|, and counts
+/// the subsets summing to |target|. Fails rather than use more than
+/// |memory_budget| bytes.
+pub fn solve(target: i64, nums: &[i64], memory_budget: usize) -> Result<Report> {
+    let target = check_input(target, nums)?;
+
+    check_budget(memory_needed(target as i64, nums), memory_budget)?;
+
+    let items: Vec<usize> = nums.iter()
+        .map(|num| *num as usize)
+        .filter(|num| *num <= target)
+        .collect();
+
+    let mut smallest = vec![u32::MAX; target + 1];
+    // Counts that overflow saturate at u128::MAX.
+    let mut count = vec![0u128; target + 1];
+    let mut improved: Vec<Bitset> = Vec::with_capacity(items.len());
+    smallest[0] = 0;
+    count[0] = 1;
+
+    // Classic 0/1 knapsack: going through the sums downwards makes
+    // sure each entry is used at most once.
+    for item in &items {
+        let mut bits = Bitset::new(target + 1);
+
+        for sum in (*item..=target).rev() {
+            let prev = sum - item;
+
+            count[sum] = count[sum].saturating_add(count[prev]);
+
+            if smallest[prev] != u32::MAX && smallest[prev] + 1 < smallest[sum] {
+                smallest[sum] = smallest[prev] + 1;
+                bits.set(sum);
+            }
+        }
+
+        improved.push(bits);
+    }
+
+    // Walk the entries backwards: an entry belongs to the smallest
+    // subset if it improved the remaining sum when it was added.
+    let smallest_subset = if smallest[target] == u32::MAX {
+        None
+    } else {
+        let mut subset = Vec::new();
+        let mut sum = target;
+        for (item, bits) in items.iter().zip(improved.iter()).rev() {
+            if sum > 0 && bits.get(sum) {
+                subset.push(*item as i64);
+                sum -= item;
+            }
+        }
+        subset.sort_unstable();
+        Some(subset)
+    };
+
+    Ok(Report {
+        smallest: smallest_subset,
+        count: if count[target] == u128::MAX { None } else { Some(count[target]) },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [i64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    /// Counts the subsets of |nums| summing to |target| by trying all of
+    /// them.
+    fn brute_force(target: i64, nums: &[i64]) -> (Option<usize>, u128) {
+        let mut smallest = None;
+        let mut count = 0;
+        for bits in 0u32..1 << nums.len() {
+            let subset: Vec<i64> = (0..nums.len())
+                .filter(|i| bits >> i & 1 == 1)
+                .map(|i| nums[i])
+                .collect();
+            if subset.iter().sum::<i64>() == target {
+                count += 1;
+                smallest = Some(smallest.map_or(subset.len(), |n: usize| n.min(subset.len())));
+            }
+        }
+        (smallest, count)
+    }
+
+    #[test]
+    fn bitset_shift() {
+        let mut bits = Bitset::new(200);
+        bits.set(0);
+        bits.set(63);
+        bits.or_shifted(70);
+        assert!(bits.get(0) && bits.get(63) && bits.get(70) && bits.get(133));
+        assert_eq!((0..200).filter(|i| bits.get(*i)).count(), 4);
+
+        bits.or_shifted(130);
+        assert!(bits.get(130) && bits.get(193) && !bits.get(199));
+        assert_eq!((0..200).filter(|i| bits.get(*i)).count(), 6);
+    }
+
+    #[test]
+    fn exists_example() {
+        assert!(exists(2020, &EXAMPLE, DEFAULT_MEMORY_BUDGET).unwrap());
+        assert!(exists(1721 + 1456, &EXAMPLE, DEFAULT_MEMORY_BUDGET).unwrap());
+        assert!(!exists(1, &EXAMPLE, DEFAULT_MEMORY_BUDGET).unwrap());
+        assert!(exists(0, &[], DEFAULT_MEMORY_BUDGET).unwrap());
+    }
+
+    #[test]
+    fn solve_example() {
+        let report = solve(2020, &EXAMPLE, DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.smallest, Some(vec![299, 1721]));
+        assert_eq!(report.count, Some(2));
+    }
+
+    #[test]
+    fn solve_zero() {
+        let report = solve(0, &[3, 0, 0], DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.smallest, Some(vec![]));
+        assert_eq!(report.count, Some(4));
+
+        let report = solve(5, &[], DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.smallest, None);
+        assert_eq!(report.count, Some(0));
+    }
+
+    #[test]
+    fn solve_duplicates() {
+        let report = solve(4, &[2, 2, 2, 1, 3, 4], DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.smallest, Some(vec![4]));
+        // {4}, {1, 3} and each of the three pairs of 2s.
+        assert_eq!(report.count, Some(5));
+    }
+
+    #[test]
+    fn solve_matches_brute_force() {
+        let nums = [7, 4, 3, 3, 0, 12, 9, 5, 1, 8, 2, 2];
+        for target in 0..=60 {
+            let (smallest, count) = brute_force(target, &nums);
+            let report = solve(target, &nums, DEFAULT_MEMORY_BUDGET).unwrap();
+            assert_eq!(report.count, Some(count), "{}", target);
+            assert_eq!(report.smallest.as_ref().map(|s| s.len()), smallest, "{}", target);
+            if let Some(subset) = report.smallest {
+                assert_eq!(subset.iter().sum::<i64>(), target);
+            }
+            assert_eq!(exists(target, &nums, DEFAULT_MEMORY_BUDGET).unwrap(), count > 0);
+        }
+    }
+
+    #[test]
+    fn negative_entries() {
+        assert!(matches!(exists(3, &[1, -2, 4], DEFAULT_MEMORY_BUDGET), Err(Error::Unsupported(_))));
+        assert!(matches!(exists(-1, &[1, 2], DEFAULT_MEMORY_BUDGET), Err(Error::Unsupported(_))));
+        assert!(matches!(solve(3, &[1, -2, 4], DEFAULT_MEMORY_BUDGET), Err(Error::Unsupported(_))));
+        assert!(matches!(solve(-1, &[1, 2], DEFAULT_MEMORY_BUDGET), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn memory_budget() {
+        let needed = memory_needed(2020, &EXAMPLE);
+        assert!(solve(2020, &EXAMPLE, needed).is_ok());
+        assert!(matches!(solve(2020, &EXAMPLE, needed - 1), Err(Error::Unsupported(_))));
+
+        let needed = Bitset::size_of(2021);
+        assert!(exists(2020, &EXAMPLE, needed).is_ok());
+        assert!(matches!(exists(2020, &EXAMPLE, needed - 1), Err(Error::Unsupported(_))));
+        assert!(matches!(exists(i64::MAX, &EXAMPLE, DEFAULT_MEMORY_BUDGET), Err(Error::Unsupported(_))));
+        assert!(matches!(solve(i64::MAX, &EXAMPLE, DEFAULT_MEMORY_BUDGET), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn count_overflow() {
+        // 2^128 subsets of 128 zeros sum to zero, one more than u128
+        // holds.
+        let report = solve(1, &[0; 128], DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.count, Some(0));
+        let report = solve(0, &[0; 128], DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.count, None);
+        let report = solve(0, &[0; 127], DEFAULT_MEMORY_BUDGET).unwrap();
+        assert_eq!(report.count, Some(1 << 127));
+    }
+}
@@ -0,0 +1,172 @@
+use common::input::Grid;
+use common::{Answer, Error, Input, Result, Solution};
+
+pub mod path;
+pub mod render;
+pub mod search;
+pub mod slope;
+
+pub use slope::{parse_slopes, Slope};
+
+/// The slope checked by the first part of the puzzle.
+pub const SLOPE: Slope = Slope::down(3, 1);
+
+/// The slopes checked by the second part of the puzzle.
+pub const SLOPES: [Slope; 5] = [
+    Slope::down(1, 1),
+    Slope::down(3, 1),
+    Slope::down(5, 1),
+    Slope::down(7, 1),
+    Slope::down(1, 2),
+];
+
+/// The character of a tree on the map.
+pub const TREE: u8 = b'#';
+
+/// The character of an open square on the map.
+pub const OPEN: u8 = b'.';
+
+/// The map of the area, which repeats itself horizontally.
+#[derive(Debug)]
+pub struct Map {
+    grid: Grid,
+
+    pub w: usize,
+    pub h: usize,
+}
+
+impl Map {
+    /// Create a Map from the puzzle input.
+    pub fn from_input(input: &Input) -> Result<Map> {
+        let grid = input.grid()?;
+
+        for y in 0..grid.height {
+            if let Some(x) = grid.row(y).iter().position(|c| *c != TREE && *c != OPEN) {
+                let c = grid.get(x, y) as char;
+                return Err(input.error(y + 1, x + 1, c.to_string(),
+                    "invalid map item, expected '.' or '#'"));
+            }
+        }
+
+        Ok(Map {
+            w: grid.width,
+            h: grid.height,
+            grid,
+        })
+    }
+
+    /// Returns whether there is a tree at (x, y). The map repeats
+    /// itself on both sides, so |x| can be negative.
+    pub fn is_tree(&self, x: i64, y: usize) -> bool {
+        let map_x = x.rem_euclid(self.w as i64) as usize;
+        self.grid.get(map_x, y % self.h) == TREE
+    }
+
+    /// Returns the slope visiting the same squares as |slope|, but
+    /// going less than the width of the map sideways at each step.
+    pub fn reduce(&self, slope: Slope) -> Slope {
+        let w = self.w as i64;
+        if slope.dx().unsigned_abs() < self.w as u64 {
+            slope
+        } else {
+            Slope::down(slope.dx().rem_euclid(w), slope.dy())
+        }
+    }
+
+    /// Counts the trees encountered when going down the map
+    /// following |slope|.
+    pub fn count_trees(&self, slope: Slope) -> u64 {
+        let dx = slope.dx().rem_euclid(self.w as i64);
+        let mut x = 0;
+        let mut y = 0;
+        let mut num_trees = 0;
+
+        while y < self.h {
+            if self.is_tree(x, y) {
+                num_trees += 1;
+            }
+            x = (x + dx) % self.w as i64;
+            y = y.saturating_add(slope.dy());
+        }
+
+        num_trees
+    }
+
+    /// Returns the squares visited when going down the map following
+    /// |slope|, their abscissas not being wrapped around its width.
+    /// Slopes going a whole width or more sideways are reduced first,
+    /// see reduce().
+    pub fn traverse(&self, slope: Slope) -> Vec<(i64, usize)> {
+        let slope = self.reduce(slope);
+        (0..self.h).step_by(slope.dy())
+            .enumerate()
+            .map(|(i, y)| (i as i64 * slope.dx(), y))
+            .collect()
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Map;
+
+    fn parse(input: &Input) -> Result<Self::Input> {
+        Map::from_input(input)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        Ok(map.count_trees(SLOPE).into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        let res = SLOPES.iter()
+            .try_fold(1u64, |acc, slope| acc.checked_mul(map.count_trees(*slope)))
+            .ok_or_else(|| Error::Unsolvable("product overflows".to_string()))?;
+        Ok(res.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(text: &str) -> Result<Map> {
+        Map::from_input(&Input::from_string("map", text.to_string()))
+    }
+
+    #[test]
+    fn crlf() {
+        let map = map("..#\r\n#..\r\n").unwrap();
+        assert_eq!((map.w, map.h), (3, 2));
+        assert!(map.is_tree(2, 0) && map.is_tree(3, 1) && !map.is_tree(-1, 1));
+    }
+
+    #[test]
+    fn trailing_newlines() {
+        let map = map("..#\n#..\n\n\n").unwrap();
+        assert_eq!((map.w, map.h), (3, 2));
+        assert_eq!(map.count_trees(Slope::down(3, 1)), 1);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = map("..#\n#.\n...\n").unwrap_err();
+        assert!(matches!(error, Error::RaggedRow { expected: 3, found: 2, .. }), "{:?}", error);
+        assert_eq!(error.to_string(), "map:2:3: row has width 2, expected 3");
+    }
+
+    #[test]
+    fn invalid_items() {
+        let error = map("..#\n#.X\n").unwrap_err();
+        assert_eq!(error.to_string(), "map:2:3: invalid map item, expected '.' or '#' (found 'X')");
+    }
+
+    #[test]
+    fn product_overflow() {
+        // Every slope hits a tree on each of its ~10^4 steps down a
+        // column of trees, the product of which exceeds u64.
+        let map = map(&"#\n".repeat(10_001)).unwrap();
+        let error = Day03::part2(&map).unwrap_err();
+        assert!(matches!(&error, Error::Unsolvable(message) if message == "product overflows"), "{:?}", error);
+    }
+}
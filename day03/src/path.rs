@@ -0,0 +1,223 @@
+use common::{Error, Result};
+
+use crate::{Map, Slope};
+
+/// A path down the map, as the squares it visits. Abscissas are not
+/// wrapped around the width of the map, so that the path can be drawn
+/// across the repetitions of the map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path {
+    pub squares: Vec<(i64, usize)>,
+    pub trees: u64,
+}
+
+/// The moves allowed by default: one square down, and up to one square
+/// left or three squares right.
+pub fn default_moves() -> Vec<Slope> {
+    (-1..=3).map(|dx| Slope::down(dx, 1)).collect()
+}
+
+/// Finds the path from the top-left square to the bottom of the map
+/// encountering the fewest trees, each step following one of |moves|.
+///
+/// Since every move goes down, the squares form a directed acyclic
+/// graph once wrapped around the width of the map, and the cheapest
+/// path to each square is computed row by row.
+pub fn find_path(map: &Map, moves: &[Slope]) -> Result<Path> {
+    if moves.is_empty() {
+        return Err(Error::Unsupported("no move is allowed".to_string()));
+    }
+
+    // Moves going a whole width or more sideways are reduced, so that
+    // abscissas cannot overflow.
+    let moves: Vec<Slope> = moves.iter().map(|m| map.reduce(*m)).collect();
+    let w = map.w as i64;
+    let index = |x: i64, y: usize| y * map.w + x.rem_euclid(w) as usize;
+
+    // The fewest trees encountered to reach each square, and the index
+    // of the move reaching it from its predecessor on that path.
+    let mut trees: Vec<Option<u64>> = vec![None; map.w * map.h];
+    let mut via: Vec<usize> = vec![0; map.w * map.h];
+    trees[0] = Some(map.is_tree(0, 0) as u64);
+
+    let mut best: Option<(u64, i64, usize)> = None;
+
+    for y in 0..map.h {
+        for x in 0..w {
+            let cost = match trees[index(x, y)] {
+                Some(cost) => cost,
+                None => continue,
+            };
+
+            for (i, m) in moves.iter().enumerate() {
+                let (next_x, next_y) = (x + m.dx(), y.saturating_add(m.dy()));
+                if next_y >= map.h {
+                    if best.is_none_or(|(b, _, _)| cost < b) {
+                        best = Some((cost, x, y));
+                    }
+                    continue;
+                }
+
+                let next_cost = cost + map.is_tree(next_x, next_y) as u64;
+                let next = index(next_x, next_y);
+                if trees[next].is_none_or(|c| next_cost < c) {
+                    trees[next] = Some(next_cost);
+                    via[next] = i;
+                }
+            }
+        }
+    }
+
+    let (cost, mut x, mut y) = best
+        .ok_or_else(|| Error::Unsolvable("the bottom of the map is unreachable".to_string()))?;
+
+    // Walk the path back to the top-left square, then unwrap it.
+    let mut steps = Vec::new();
+    while (x, y) != (0, 0) {
+        let m = moves[via[index(x, y)]];
+        steps.push(m);
+        x = (x - m.dx()).rem_euclid(w);
+        y -= m.dy();
+    }
+
+    let mut squares = vec![(0, 0)];
+    for m in steps.iter().rev() {
+        let (x, y) = squares[squares.len() - 1];
+        squares.push((x + m.dx(), y + m.dy()));
+    }
+
+    Ok(Path {
+        squares,
+        trees: cost,
+    })
+}
+
+impl Path {
+    /// Draws the path over as many repetitions of |map| as it crosses,
+    /// marking the open squares it visits with 'O' and the trees with
+    /// 'X', in color when |colored|.
+    pub fn render(&self, map: &Map, colored: bool) -> String {
+        map.render_ascii(std::slice::from_ref(&self.squares), colored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Input;
+
+    const EXAMPLE: &str = "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+    fn map(text: &str) -> Map {
+        Map::from_input(&Input::from_string("map", text.to_string())).unwrap()
+    }
+
+    /// Returns the fewest trees encountered from (|x|, |y|) to the
+    /// bottom of the map by trying every sequence of moves.
+    fn brute_force(map: &Map, moves: &[Slope], x: i64, y: usize) -> u64 {
+        let here = map.is_tree(x, y) as u64;
+        here + moves.iter()
+            .map(|m| if y + m.dy() < map.h { brute_force(map, moves, x + m.dx(), y + m.dy()) } else { 0 })
+            .min()
+            .unwrap()
+    }
+
+    /// Checks |path| starts at the top-left square, follows |moves| until
+    /// one of them leaves the map and encounters |path.trees| trees.
+    fn check_path(map: &Map, moves: &[Slope], path: &Path) {
+        assert_eq!(path.squares[0], (0, 0));
+        for pair in path.squares.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            assert!(moves.iter().any(|m| x0 + m.dx() == x1 && y0 + m.dy() == y1), "{:?}", pair);
+        }
+        let (_, last_y) = path.squares[path.squares.len() - 1];
+        assert!(moves.iter().any(|m| last_y + m.dy() >= map.h));
+        let trees = path.squares.iter().filter(|(x, y)| map.is_tree(*x, *y)).count() as u64;
+        assert_eq!(path.trees, trees);
+    }
+
+    #[test]
+    fn example() {
+        let map = map(EXAMPLE);
+        let moves = default_moves();
+        let path = find_path(&map, &moves).unwrap();
+        check_path(&map, &moves, &path);
+        assert_eq!(path.trees, 0);
+
+        let moves = [Slope::down(1, 1), Slope::down(3, 1), Slope::down(1, 2)];
+        let path = find_path(&map, &moves).unwrap();
+        check_path(&map, &moves, &path);
+        assert_eq!(path.trees, brute_force(&map, &moves, 0, 0));
+    }
+
+    #[test]
+    fn single_move() {
+        let map = map(EXAMPLE);
+        for slope in crate::SLOPES.iter() {
+            let path = find_path(&map, &[*slope]).unwrap();
+            assert_eq!(path.squares, map.traverse(*slope));
+            assert_eq!(path.trees, map.count_trees(*slope));
+        }
+    }
+
+    #[test]
+    fn wraps_around() {
+        // The only path avoiding trees goes left, across the left edge
+        // of the map.
+        let map = map("\
+.####
+####.
+###.#
+##.##
+");
+        let moves = [Slope::down(0, 1), Slope::down(1, 1), Slope::down(-1, 1)];
+        let path = find_path(&map, &moves).unwrap();
+        check_path(&map, &moves, &path);
+        assert_eq!(path.trees, 0);
+        assert_eq!(path.squares, vec![(0, 0), (-1, 1), (-2, 2), (-3, 3)]);
+    }
+
+    #[test]
+    fn unavoidable_trees() {
+        let map = map("\
+.#.
+###
+.##
+###
+##.
+");
+        let moves = [Slope::down(1, 1), Slope::down(0, 2)];
+        let path = find_path(&map, &moves).unwrap();
+        check_path(&map, &moves, &path);
+        assert_eq!(path.trees, brute_force(&map, &moves, 0, 0));
+        assert_eq!(path.trees, 1);
+    }
+
+    #[test]
+    fn large_moves() {
+        let map = map(EXAMPLE);
+        let moves = [Slope::down(i64::MAX, 1), Slope::down(i64::MIN, 3)];
+        let path = find_path(&map, &moves).unwrap();
+        let reduced: Vec<Slope> = moves.iter().map(|m| map.reduce(*m)).collect();
+        check_path(&map, &reduced, &path);
+        assert_eq!(path.trees, brute_force(&map, &reduced, 0, 0));
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let map = map(EXAMPLE);
+        assert!(matches!(find_path(&map, &[]), Err(Error::Unsupported(_))));
+    }
+}
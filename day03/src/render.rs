@@ -0,0 +1,195 @@
+//! Drawing of paths over the map, as terminal text or as SVG.
+//!
+//! Paths are given as the squares they visit, with abscissas not
+//! wrapped around the width of the map: the map is repeated as many
+//! times as needed to draw them. Paths are drawn in the order they are
+//! given, a square visited by several paths taking the color of the
+//! first one. The output only depends on the map and the paths.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{Map, TREE};
+
+/// ANSI colors of the paths, cycled through.
+const ANSI_COLORS: [&str; 6] = ["33", "36", "35", "32", "34", "31"];
+
+/// SVG colors of the paths, cycled through.
+const SVG_COLORS: [&str; 6] = ["#e6194b", "#4363d8", "#f58231", "#911eb4", "#42d4f4", "#f032e6"];
+
+/// Size of a square in the SVG output, in pixels.
+const SVG_CELL: usize = 10;
+
+/// The squares visited by some paths, and the repetitions of the map
+/// they cross.
+struct Overlay {
+    /// Index of the first path visiting each square.
+    visits: HashMap<(i64, usize), usize>,
+    min_x: i64,
+    max_x: i64,
+}
+
+impl Overlay {
+    fn new(map: &Map, paths: &[Vec<(i64, usize)>]) -> Overlay {
+        let w = map.w as i64;
+        let mut visits = HashMap::new();
+        let mut min_tile = 0;
+        let mut max_tile = 0;
+
+        for (i, path) in paths.iter().enumerate() {
+            for (x, y) in path {
+                visits.entry((*x, *y)).or_insert(i);
+                min_tile = min_tile.min(x.div_euclid(w));
+                max_tile = max_tile.max(x.div_euclid(w));
+            }
+        }
+
+        Overlay {
+            visits,
+            min_x: min_tile * w,
+            max_x: (max_tile + 1) * w,
+        }
+    }
+}
+
+impl Map {
+    /// Draws |paths| over the map, marking the open squares they visit
+    /// with 'O' and the trees with 'X'. When |colored|, each path is
+    /// painted in its own ANSI color.
+    pub fn render_ascii(&self, paths: &[Vec<(i64, usize)>], colored: bool) -> String {
+        let overlay = Overlay::new(self, paths);
+        let mut res = String::new();
+
+        for y in 0..self.h {
+            for x in overlay.min_x..overlay.max_x {
+                let tree = self.is_tree(x, y);
+                match overlay.visits.get(&(x, y)) {
+                    Some(i) => {
+                        let marker = if tree { 'X' } else { 'O' };
+                        if colored {
+                            let color = ANSI_COLORS[i % ANSI_COLORS.len()];
+                            let _ = write!(res, "\x1b[1;{}m{}\x1b[0m", color, marker);
+                        } else {
+                            res.push(marker);
+                        }
+                    },
+                    None =>
+                        res.push(if tree { TREE as char } else { '.' }),
+                }
+            }
+            res.push('\n');
+        }
+
+        res
+    }
+
+    /// Draws |paths| over the map as a standalone SVG document. Trees
+    /// are dark squares, the open squares visited by a path are circled
+    /// and the trees it hits are crossed, in the color of the path.
+    /// Dashed lines separate the repetitions of the map.
+    pub fn render_svg(&self, paths: &[Vec<(i64, usize)>]) -> String {
+        let overlay = Overlay::new(self, paths);
+        let width = (overlay.max_x - overlay.min_x) as usize * SVG_CELL;
+        let height = self.h * SVG_CELL;
+        let mut res = String::new();
+
+        let _ = writeln!(res, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width, h = height);
+        let _ = writeln!(res, r##"<rect width="{}" height="{}" fill="#ffffff"/>"##, width, height);
+
+        for y in 0..self.h {
+            for x in overlay.min_x..overlay.max_x {
+                if self.is_tree(x, y) {
+                    let _ = writeln!(res, r##"<rect x="{}" y="{}" width="{c}" height="{c}" fill="#2f5d2f"/>"##,
+                        (x - overlay.min_x) as usize * SVG_CELL, y * SVG_CELL, c = SVG_CELL);
+                }
+            }
+        }
+
+        for tile in (overlay.min_x..overlay.max_x).step_by(self.w).skip(1) {
+            let px = (tile - overlay.min_x) as usize * SVG_CELL;
+            let _ = writeln!(res, r##"<line x1="{x}" y1="0" x2="{x}" y2="{}" stroke="#999999" stroke-dasharray="4 4"/>"##,
+                height, x = px);
+        }
+
+        let mut visits: Vec<_> = overlay.visits.iter().collect();
+        visits.sort_by_key(|((x, y), _)| (*y, *x));
+        for ((x, y), i) in visits {
+            let color = SVG_COLORS[i % SVG_COLORS.len()];
+            let left = (x - overlay.min_x) as usize * SVG_CELL;
+            let top = y * SVG_CELL;
+            if self.is_tree(*x, *y) {
+                let _ = writeln!(res, r#"<path d="M{} {}l{d} {d}M{} {}l{d} -{d}" stroke="{}" stroke-width="2"/>"#,
+                    left + 1, top + 1, left + 1, top + SVG_CELL - 1, color, d = SVG_CELL - 2);
+            } else {
+                let _ = writeln!(res, r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                    left + SVG_CELL / 2, top + SVG_CELL / 2, SVG_CELL / 2 - 1, color);
+            }
+        }
+
+        res.push_str("</svg>\n");
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Slope;
+    use common::Input;
+
+    fn map() -> Map {
+        Map::from_input(&Input::from_string("map", "..#\n#..\n.#.\n".to_string())).unwrap()
+    }
+
+    fn paths(map: &Map) -> Vec<Vec<(i64, usize)>> {
+        vec![map.traverse(Slope::down(2, 1)), map.traverse(Slope::down(-1, 1))]
+    }
+
+    #[test]
+    fn ascii() {
+        let map = map();
+        assert_eq!(map.render_ascii(&paths(&map), false), concat!(
+            "..#O.#..#\n",
+            "#.O#.O#..\n",
+            ".X..#..X.\n",
+        ));
+    }
+
+    #[test]
+    fn ascii_colored() {
+        let map = map();
+        let paths = vec![map.traverse(Slope::down(1, 1)), map.traverse(Slope::down(1, 1))];
+        assert_eq!(map.render_ascii(&paths, true), concat!(
+            "\x1b[1;33mO\x1b[0m.#\n",
+            "#\x1b[1;33mO\x1b[0m.\n",
+            ".#\x1b[1;33mO\x1b[0m\n",
+        ));
+    }
+
+    #[test]
+    fn svg() {
+        let map = map();
+        assert_eq!(map.render_svg(&paths(&map)), concat!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="90" height="30" viewBox="0 0 90 30">"##, "\n",
+            r##"<rect width="90" height="30" fill="#ffffff"/>"##, "\n",
+            r##"<rect x="20" y="0" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="50" y="0" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="80" y="0" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="0" y="10" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="30" y="10" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="60" y="10" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="10" y="20" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="40" y="20" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<rect x="70" y="20" width="10" height="10" fill="#2f5d2f"/>"##, "\n",
+            r##"<line x1="30" y1="0" x2="30" y2="30" stroke="#999999" stroke-dasharray="4 4"/>"##, "\n",
+            r##"<line x1="60" y1="0" x2="60" y2="30" stroke="#999999" stroke-dasharray="4 4"/>"##, "\n",
+            r##"<circle cx="35" cy="5" r="4" fill="none" stroke="#e6194b" stroke-width="2"/>"##, "\n",
+            r##"<circle cx="25" cy="15" r="4" fill="none" stroke="#4363d8" stroke-width="2"/>"##, "\n",
+            r##"<circle cx="55" cy="15" r="4" fill="none" stroke="#e6194b" stroke-width="2"/>"##, "\n",
+            r##"<path d="M11 21l8 8M11 29l8 -8" stroke="#4363d8" stroke-width="2"/>"##, "\n",
+            r##"<path d="M71 21l8 8M71 29l8 -8" stroke="#e6194b" stroke-width="2"/>"##, "\n",
+            r##"</svg>"##, "\n",
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+use common::{Error, Result};
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use crate::{Map, Slope};
+
+/// The trees encountered when following a slope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ranked {
+    pub slope: Slope,
+    pub trees: u64,
+    /// Number of squares visited before reaching the bottom of the map.
+    pub steps: usize,
+}
+
+/// Follows every slope within |dx| and |dy| and ranks them from the
+/// safest to the most dangerous, i.e. by increasing number of trees
+/// encountered, then by increasing path length.
+///
+/// Slopes going down by the same |dy| visit the same rows, and those
+/// whose |dx| are equal modulo the width of the map visit the same
+/// squares, so each such class of slopes is only followed once.
+pub fn search(map: &Map, dx: RangeInclusive<i64>, dy: RangeInclusive<usize>) -> Result<Vec<Ranked>> {
+    if *dy.start() == 0 {
+        return Err(Error::Unsupported("slopes must go down".to_string()));
+    }
+    if dx.is_empty() {
+        return Err(Error::Unsupported(format!("empty dx range: {} > {}", dx.start(), dx.end())));
+    }
+    if dy.is_empty() {
+        return Err(Error::Unsupported(format!("empty dy range: {} > {}", dy.start(), dy.end())));
+    }
+
+    let w = map.w as i64;
+    let mut res = Vec::new();
+
+    for dy in dy {
+        let rows: Vec<usize> = (0..map.h).step_by(dy).collect();
+        let mut trees_by_shift: HashMap<i64, u64> = HashMap::new();
+
+        for dx in dx.clone() {
+            let shift = dx.rem_euclid(w);
+            let trees = *trees_by_shift.entry(shift).or_insert_with(|| {
+                rows.iter()
+                    .enumerate()
+                    .filter(|(i, y)| map.is_tree(*i as i64 * shift, **y))
+                    .count() as u64
+            });
+
+            res.push(Ranked {
+                slope: Slope::down(dx, dy),
+                trees,
+                steps: rows.len(),
+            });
+        }
+    }
+
+    res.sort_by_key(|ranked| (ranked.trees, ranked.steps, ranked.slope));
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::Input;
+
+    fn map() -> Map {
+        Map::from_input(&Input::from_string("map", "....\n#...\n..#.\n.#..\n".to_string())).unwrap()
+    }
+
+    fn ranked(dx: i64, dy: usize, trees: u64, steps: usize) -> Ranked {
+        Ranked {
+            slope: Slope::down(dx, dy),
+            trees,
+            steps,
+        }
+    }
+
+    #[test]
+    fn ranking() {
+        assert_eq!(search(&map(), 0..=4, 1..=2).unwrap(), vec![
+            // Fewest trees first, then shortest paths, then by slope.
+            ranked(0, 2, 0, 2),
+            ranked(1, 2, 0, 2),
+            ranked(3, 2, 0, 2),
+            ranked(4, 2, 0, 2),
+            ranked(2, 1, 0, 4),
+            ranked(2, 2, 1, 2),
+            ranked(0, 1, 1, 4),
+            ranked(1, 1, 1, 4),
+            ranked(4, 1, 1, 4),
+            ranked(3, 1, 2, 4),
+        ]);
+    }
+
+    #[test]
+    fn equal_shifts() {
+        let map = map();
+        let ranking = search(&map, -8..=8, 1..=3).unwrap();
+        assert_eq!(ranking.len(), 17 * 3);
+
+        for r in &ranking {
+            assert_eq!(r.trees, map.count_trees(r.slope), "{}", r.slope);
+            let shifted = ranking.iter()
+                .find(|other| other.slope == Slope::down(r.slope.dx().rem_euclid(4), r.slope.dy()))
+                .unwrap();
+            assert_eq!((r.trees, r.steps), (shifted.trees, shifted.steps), "{}", r.slope);
+        }
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let map = map();
+        assert!(matches!(search(&map, 0..=4, 0..=2), Err(Error::Unsupported(_))));
+        assert!(matches!(search(&map, RangeInclusive::new(5, 4), 1..=2), Err(Error::Unsupported(_))));
+        assert!(matches!(search(&map, 0..=4, RangeInclusive::new(5, 4)), Err(Error::Unsupported(_))));
+        assert_eq!(search(&map, 2..=2, 1..=1).unwrap(), vec![ranked(2, 1, 0, 4)]);
+    }
+}
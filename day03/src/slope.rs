@@ -0,0 +1,169 @@
+use common::{Error, Input, Result};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A slope going |dx| squares right, or left when negative, for every
+/// |dy| squares down. Slopes always go down, i.e. |dy| > 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Slope {
+    dx: i64,
+    dy: usize,
+}
+
+impl Slope {
+    /// Creates a slope, unless it does not go down.
+    pub fn new(dx: i64, dy: usize) -> Result<Slope> {
+        if dy == 0 {
+            return Err(Error::Unsupported("slopes must go down".to_string()));
+        }
+
+        Ok(Slope { dx, dy })
+    }
+
+    /// Creates a slope known to go down, such as a constant or a slope
+    /// derived from another one. Panics if |dy| is 0.
+    pub(crate) const fn down(dx: i64, dy: usize) -> Slope {
+        assert!(dy > 0, "slopes must go down");
+        Slope { dx, dy }
+    }
+
+    pub fn dx(&self) -> i64 {
+        self.dx
+    }
+
+    pub fn dy(&self) -> usize {
+        self.dy
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Parses an integer of a slope, located at |column|.
+fn parse_int<T: FromStr>(column: usize, s: &str) -> Result<T>
+where
+    T::Err: fmt::Display,
+{
+    s.trim().parse::<T>()
+        .map_err(|e| Error::parse(column, s, format!("invalid slope: {}", e)))
+}
+
+impl FromStr for Slope {
+    type Err = Error;
+
+    /// Parses a slope given either as steps, "dx,dy", or as a number of
+    /// squares right per square down, "dx" or "dx/dy". The latter is
+    /// reduced, so "6/4" goes 3 right for every 2 down.
+    fn from_str(s: &str) -> Result<Slope> {
+        let (dx, dy) = if let Some(ind) = s.find(',') {
+            (parse_int(1, &s[..ind])?, parse_int(ind + 2, &s[ind + 1..])?)
+        } else if let Some(ind) = s.find('/') {
+            let dx: i64 = parse_int(1, &s[..ind])?;
+            let dy: usize = parse_int(ind + 2, &s[ind + 1..])?;
+            let div = gcd(dx.unsigned_abs(), dy as u64).max(1);
+            ((dx as i128 / div as i128) as i64, dy / div as usize)
+        } else {
+            (parse_int(1, s)?, 1)
+        };
+
+        Slope::new(dx, dy)
+            .map_err(|_| Error::parse(1, s, "invalid slope: must go down"))
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("{},{}", self.dx, self.dy))
+    }
+}
+
+/// Parses a file of slopes, one per line. Blank lines and lines
+/// starting with '#' are ignored.
+pub fn parse_slopes(input: &Input) -> Result<Vec<Slope>> {
+    let mut slopes = Vec::new();
+
+    for line in input.lines() {
+        let text = line.text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        let slope = text.parse::<Slope>()
+            .map_err(|e| input.locate(e, line.number))?;
+        slopes.push(slope);
+    }
+
+    Ok(slopes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Slope {
+        s.parse().unwrap()
+    }
+
+    fn parse_error(s: &str) -> String {
+        s.parse::<Slope>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(Slope::new(3, 1).unwrap(), Slope::down(3, 1));
+        assert!(matches!(Slope::new(3, 0), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(parse("3,1"), Slope::down(3, 1));
+        assert_eq!(parse(" 1 , 2 "), Slope::down(1, 2));
+        assert_eq!(parse("-2,3"), Slope::down(-2, 3));
+        assert_eq!(parse("0,5"), Slope::down(0, 5));
+        // Steps are kept as given.
+        assert_eq!(parse("6,4"), Slope::down(6, 4));
+    }
+
+    #[test]
+    fn ratios() {
+        assert_eq!(parse("3"), Slope::down(3, 1));
+        assert_eq!(parse("-1"), Slope::down(-1, 1));
+        assert_eq!(parse("6/4"), Slope::down(3, 2));
+        assert_eq!(parse("-6/4"), Slope::down(-3, 2));
+        assert_eq!(parse("0/4"), Slope::down(0, 1));
+        assert_eq!(parse("5/3"), Slope::down(5, 3));
+        assert_eq!(parse("-9223372036854775808/9223372036854775808"), Slope::down(-1, 1));
+    }
+
+    #[test]
+    fn invalid_slopes() {
+        assert_eq!(parse_error("3,0"), "column 1: invalid slope: must go down (found '3,0')");
+        assert_eq!(parse_error("3/0"), "column 1: invalid slope: must go down (found '3/0')");
+        assert_eq!(parse_error("x,1"), "column 1: invalid slope: invalid digit found in string (found 'x')");
+        assert_eq!(parse_error("3,-1"), "column 3: invalid slope: invalid digit found in string (found '-1')");
+        assert_eq!(parse_error(""), "column 1: invalid slope: cannot parse integer from empty string");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Slope::down(-3, 2).to_string(), "-3,2");
+        assert_eq!(format!("{:<6}|", Slope::down(1, 1)), "1,1   |");
+    }
+
+    #[test]
+    fn slopes_file() {
+        let input = Input::from_string("slopes", "# part 2\n1,1\n\n  3\n5/1\n".to_string());
+        assert_eq!(parse_slopes(&input).unwrap(),
+            vec![Slope::down(1, 1), Slope::down(3, 1), Slope::down(5, 1)]);
+
+        let input = Input::from_string("slopes", "1,1\n# bad\n2,x\n".to_string());
+        assert_eq!(parse_slopes(&input).unwrap_err().to_string(),
+            "slopes:3:3: invalid slope: invalid digit found in string (found 'x')");
+
+        let input = Input::from_string("slopes", "1,0\n".to_string());
+        assert_eq!(parse_slopes(&input).unwrap_err().to_string(),
+            "slopes:1:1: invalid slope: must go down (found '1,0')");
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use common::{Answer, Input, Result, Solution};
+
+pub mod parser;
+pub mod report;
+pub mod schema;
+pub mod stream;
+
+pub use parser::{Mode, Parser};
+pub use report::Report;
+pub use schema::Schema;
+
+/// The value of a field, and where it is in the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub value: String,
+    pub line: usize,
+    /// The column of the value.
+    pub column: usize,
+    /// The column of the key, where the field starts.
+    pub key_column: usize,
+}
+
+/// A passport, as its fields by key.
+#[derive(Debug, Default)]
+pub struct Passport {
+    fields: BTreeMap<String, Field>,
+
+    /// The 1-based index of the record of the passport in the input.
+    pub record: usize,
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+impl Passport {
+    /// Returns the value of the field named |key|, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(|field| field.value.as_str())
+    }
+
+    /// Returns the field named |key|, if any.
+    pub fn field(&self, key: &str) -> Option<&Field> {
+        self.fields.get(key)
+    }
+
+    /// Formats the passport on a single line, with its fields in the
+    /// order of the rules of |schema| followed by the fields it does
+    /// not know about, by key.
+    pub fn to_canonical(&self, schema: &Schema) -> String {
+        let known = schema.rules.iter()
+            .filter_map(|rule| self.fields.get_key_value(&rule.name));
+        let unknown = self.fields.iter()
+            .filter(|(key, _)| schema.rule(key).is_none());
+
+        let fields: Vec<String> = known.chain(unknown)
+            .map(|(key, field)| format!("{}:{}", key, field.value))
+            .collect();
+        fields.join(" ")
+    }
+}
+
+/// Parses the passports of a batch file, where records are separated
+/// by blank lines, leniently against the rules of the puzzle.
+pub fn parse_passports(input: &Input) -> Vec<Passport> {
+    Parser::new(Schema::default_schema(), Mode::Lenient)
+        .parse(input)
+        .passports
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+
+    fn parse(input: &Input) -> Result<Self::Input> {
+        Ok(parse_passports(input))
+    }
+
+    fn part1(passports: &Self::Input) -> Result<Answer> {
+        let res = passports.iter()
+            .filter(|p| Schema::default_schema().is_complete(p))
+            .count();
+        Ok(res.into())
+    }
+
+    fn part2(passports: &Self::Input) -> Result<Answer> {
+        let res = passports.iter()
+            .filter(|p| Schema::default_schema().is_valid(p))
+            .count();
+        Ok(res.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BATCH: &str = "\
+pid:000000001 ecl:brn zzz:1 hcl:#123abc
+eyr:2025 byr:2002 hgt:180cm iyr:2015 aaa:2
+
+ecl:wat iyr:2015
+cid:99 pid:1 byr:1990
+";
+
+    #[test]
+    fn canonical() {
+        let schema = Schema::default_schema();
+        let passports = parse_passports(&Input::from_string("batch", BATCH.to_string()));
+
+        assert!(schema.is_valid(&passports[0]));
+        assert_eq!(passports[0].to_canonical(schema),
+            "byr:2002 iyr:2015 eyr:2025 hgt:180cm hcl:#123abc ecl:brn pid:000000001 aaa:2 zzz:1");
+        assert!(!schema.is_valid(&passports[1]));
+        assert_eq!(passports[1].to_canonical(schema), "byr:1990 iyr:2015 ecl:wat pid:1 cid:99");
+    }
+}
@@ -0,0 +1,258 @@
+//! Parsing of passport batch files, where records of "key:value"
+//! fields are separated by blank lines.
+
+use std::fmt;
+
+use common::input::Record;
+use common::Input;
+
+use crate::schema::{Schema, Type};
+use crate::{Field, Passport};
+
+/// How fields that do not fit the schema are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Unknown fields are accepted, duplicate fields overwrite the
+    /// earlier ones, and records with a malformed field are skipped.
+    #[default]
+    Lenient,
+    /// Every record is kept, and unknown, duplicate and malformed
+    /// fields are diagnosed. Duplicate fields are ignored, and
+    /// malformed ones are kept when only their value is malformed.
+    Strict,
+}
+
+/// What is wrong with a field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// The schema has no rule for the field.
+    Unknown,
+    /// The record already has the field, at the given line and column.
+    Duplicate(usize, usize),
+    /// The field cannot be parsed, for the given reason.
+    Malformed(String),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::Unknown =>
+                write!(f, "unknown field"),
+            Issue::Duplicate(line, column) =>
+                write!(f, "duplicate field, first set at {}:{}", line, column),
+            Issue::Malformed(reason) =>
+                write!(f, "malformed field: {}", reason),
+        }
+    }
+}
+
+/// An issue found in a field of a record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The 1-based index of the record in the input.
+    pub record: usize,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {} (found '{}')", self.line, self.column, self.issue, self.text)
+    }
+}
+
+/// The passports of a batch file, and the issues found in their fields.
+#[derive(Debug, Default)]
+pub struct Batch {
+    pub passports: Vec<Passport>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses records into passports, checking their fields against a
+/// schema.
+pub struct Parser<'a> {
+    schema: &'a Schema,
+    mode: Mode,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(schema: &'a Schema, mode: Mode) -> Parser<'a> {
+        Parser { schema, mode }
+    }
+
+    pub fn schema(&self) -> &'a Schema {
+        self.schema
+    }
+
+    /// Parses every record of |input|.
+    pub fn parse(&self, input: &Input) -> Batch {
+        let mut batch = Batch::default();
+
+        for (i, record) in input.records().iter().enumerate() {
+            if let Some(passport) = self.parse_record(i + 1, record, &mut batch.diagnostics) {
+                batch.passports.push(passport);
+            }
+        }
+
+        batch
+    }
+
+    /// Parses the |index|-th record of an input, appending the issues
+    /// found to |diagnostics|. Returns None if the record is skipped.
+    pub fn parse_record(&self, index: usize, record: &Record,
+                        diagnostics: &mut Vec<Diagnostic>) -> Option<Passport> {
+        let mut passport = Passport {
+            record: index,
+            first_line: record.first_line(),
+            last_line: record.last_line(),
+            ..Passport::default()
+        };
+
+        for line in &record.lines {
+            for text in line.text.split_whitespace() {
+                // The field is a sub-slice of the line, its offset is
+                // the column at which it starts.
+                let column = text.as_ptr() as usize - line.text.as_ptr() as usize + 1;
+                let mut diagnose = |issue| diagnostics.push(Diagnostic {
+                    record: index,
+                    line: line.number,
+                    column,
+                    text: text.to_string(),
+                    issue,
+                });
+
+                let (key, value) = match self.split(text) {
+                    Ok(field) => field,
+                    Err(_) if self.mode == Mode::Lenient => return None,
+                    Err(reason) => {
+                        diagnose(Issue::Malformed(reason));
+                        continue;
+                    },
+                };
+
+                match self.schema.rule(key) {
+                    Some(rule) if rule.kind == Type::Integer && rule.units.is_empty()
+                        && value.parse::<usize>().is_err() => {
+                        if self.mode == Mode::Lenient {
+                            return None;
+                        }
+                        diagnose(Issue::Malformed("value is not a non-negative integer".to_string()));
+                    },
+                    Some(_) => {
+                    },
+                    None if self.mode == Mode::Strict =>
+                        diagnose(Issue::Unknown),
+                    None => {
+                    },
+                }
+
+                if self.mode == Mode::Strict {
+                    if let Some(first) = passport.fields.get(key) {
+                        diagnose(Issue::Duplicate(first.line, first.key_column));
+                        continue;
+                    }
+                }
+
+                passport.fields.insert(key.to_string(), Field {
+                    value: value.to_string(),
+                    line: line.number,
+                    column: column + key.len() + 1,
+                    key_column: column,
+                });
+            }
+        }
+
+        Some(passport)
+    }
+
+    /// Splits a "key:value" field.
+    fn split<'b>(&self, text: &'b str) -> std::result::Result<(&'b str, &'b str), String> {
+        if self.mode == Mode::Lenient {
+            // Anything after a second ':' is ignored.
+            let mut parts = text.split(':');
+            let key = parts.next().unwrap_or_default();
+            let value = parts.next().ok_or("missing value")?;
+            return Ok((key, value));
+        }
+
+        let (key, value) = text.split_once(':')
+            .ok_or("expected 'key:value'")?;
+        if key.is_empty() {
+            return Err("missing key".to_string());
+        }
+        if value.is_empty() {
+            return Err("missing value".to_string());
+        }
+        if value.contains(':') {
+            return Err("more than one ':'".to_string());
+        }
+
+        Ok((key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_location() {
+        let input = Input::from_string("batch", "byr:1990 iyr:2015\n  byr:1991 eyr:2025\n".to_string());
+        let batch = Parser::new(Schema::default_schema(), Mode::Strict).parse(&input);
+
+        let first = batch.passports[0].field("byr").unwrap();
+        assert_eq!((first.line, first.key_column, first.column), (1, 1, 5));
+        assert_eq!(batch.diagnostics, vec![Diagnostic {
+            record: 1,
+            line: 2,
+            column: 3,
+            text: "byr:1991".to_string(),
+            issue: Issue::Duplicate(1, 1),
+        }]);
+        assert_eq!(batch.diagnostics[0].to_string(), "2:3: duplicate field, first set at 1:1 (found 'byr:1991')");
+    }
+
+    #[test]
+    fn lenient_skips_malformed_records() {
+        let input = Input::from_string("batch",
+            "byr:1990 cid:1\n\nbyr:-1990\n\nbyr:19x0\n\nhgt\n\nxyz:1 hgt:1:2\n".to_string());
+        let batch = Parser::new(Schema::default_schema(), Mode::Lenient).parse(&input);
+
+        let records: Vec<usize> = batch.passports.iter().map(|passport| passport.record).collect();
+        assert_eq!(records, vec![1, 5]);
+        assert_eq!(batch.passports[1].get("hgt"), Some("1"));
+        assert_eq!(batch.passports[1].get("xyz"), Some("1"));
+        assert!(batch.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn strict_diagnostics() {
+        let input = Input::from_string("batch",
+            "xyz:1 byr:-1990\n\nhgt :cm byr:1:2\n\niyr:19x0 eyr:\n".to_string());
+        let batch = Parser::new(Schema::default_schema(), Mode::Strict).parse(&input);
+
+        let diagnostics: Vec<(usize, String)> = batch.diagnostics.iter()
+            .map(|diagnostic| (diagnostic.record, diagnostic.to_string()))
+            .collect();
+        assert_eq!(diagnostics, vec![
+            (1, "1:1: unknown field (found 'xyz:1')".to_string()),
+            (1, "1:7: malformed field: value is not a non-negative integer (found 'byr:-1990')".to_string()),
+            (2, "3:1: malformed field: expected 'key:value' (found 'hgt')".to_string()),
+            (2, "3:5: malformed field: missing key (found ':cm')".to_string()),
+            (2, "3:9: malformed field: more than one ':' (found 'byr:1:2')".to_string()),
+            (3, "5:1: malformed field: value is not a non-negative integer (found 'iyr:19x0')".to_string()),
+            (3, "5:10: malformed field: missing value (found 'eyr:')".to_string()),
+        ]);
+
+        // Every record is kept, and so are the fields whose value only
+        // is malformed.
+        assert_eq!(batch.passports.len(), 3);
+        assert_eq!(batch.passports[0].get("xyz"), Some("1"));
+        assert_eq!(batch.passports[0].get("byr"), Some("-1990"));
+        assert_eq!(batch.passports[1].fields.len(), 0);
+        assert_eq!(batch.passports[2].get("iyr"), Some("19x0"));
+        assert_eq!(batch.passports[2].get("eyr"), None);
+    }
+}
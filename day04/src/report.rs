@@ -0,0 +1,211 @@
+//! Explanation of why passports are invalid.
+
+use serde::Serialize;
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::schema::Failure;
+use crate::Passport;
+
+/// The fields of an invalid passport that are missing or do not
+/// satisfy their rule.
+#[derive(Clone, Debug, Serialize)]
+pub struct InvalidPassport {
+    pub record: usize,
+    pub first_line: usize,
+    pub last_line: usize,
+    pub failures: Vec<Failure>,
+}
+
+/// A record skipped in lenient mode, because one of its fields is
+/// malformed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkippedRecord {
+    pub record: usize,
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+/// The outcome of validating a batch of passports.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Report {
+    pub valid: usize,
+    pub invalid: Vec<InvalidPassport>,
+    pub skipped: Vec<SkippedRecord>,
+    /// The number of passports failing because of each field.
+    pub failures_per_field: BTreeMap<String, usize>,
+}
+
+impl Report {
+    /// Accounts for the |record|-th record, spanning |first_line| to
+    /// |last_line|, which was skipped.
+    pub fn skip(&mut self, record: usize, first_line: usize, last_line: usize) {
+        self.skipped.push(SkippedRecord {
+            record,
+            first_line,
+            last_line,
+        });
+    }
+
+    /// Accounts for |passport|, whose fields failing validation are
+    /// |failures|.
+    pub fn add(&mut self, passport: &Passport, failures: Vec<Failure>) {
+        if failures.is_empty() {
+            self.valid += 1;
+            return;
+        }
+
+        for failure in &failures {
+            *self.failures_per_field.entry(failure.field.clone()).or_insert(0) += 1;
+        }
+        self.invalid.push(InvalidPassport {
+            record: passport.record,
+            first_line: passport.first_line,
+            last_line: passport.last_line,
+            failures,
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for passport in &self.invalid {
+            writeln!(f, "record {} (lines {}-{}):", passport.record,
+                passport.first_line, passport.last_line)?;
+            for failure in &passport.failures {
+                match (&failure.value, failure.line, failure.column) {
+                    (Some(value), Some(line), Some(column)) =>
+                        writeln!(f, "  {}:{}: {}: {} (found '{}')", line, column,
+                            failure.field, failure.violation, value)?,
+                    _ =>
+                        writeln!(f, "  {}: {}", failure.field, failure.violation)?,
+                }
+            }
+        }
+        for skipped in &self.skipped {
+            writeln!(f, "record {} (lines {}-{}): skipped, a field is malformed",
+                skipped.record, skipped.first_line, skipped.last_line)?;
+        }
+
+        writeln!(f, "failures per field:")?;
+        for (field, count) in &self.failures_per_field {
+            writeln!(f, "  {:<6} {}", field, count)?;
+        }
+        writeln!(f, "valid: {}, invalid: {}, skipped: {}",
+            self.valid, self.invalid.len(), self.skipped.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::Input;
+
+    use crate::{Mode, Parser, Schema};
+
+    const BATCH: &str = "\
+byr:1990 iyr:2015 hgt:180cm
+pid:1
+
+byr:x9
+
+byr:2002 iyr:2015 eyr:2025 hgt:180cm hcl:#123abc ecl:brn pid:000000001
+
+foo
+bar
+";
+
+    /// Reports on the passports of |BATCH|, the way the day04 tool does.
+    fn report() -> Report {
+        let input = Input::from_string("batch", BATCH.to_string());
+        let schema = Schema::default_schema();
+        let parser = Parser::new(schema, Mode::Lenient);
+        let mut report = Report::default();
+
+        for (i, record) in input.records().iter().enumerate() {
+            match parser.parse_record(i + 1, record, &mut Vec::new()) {
+                Some(passport) => {
+                    let failures = schema.validate(&passport);
+                    report.add(&passport, failures);
+                },
+                None =>
+                    report.skip(i + 1, record.first_line(), record.last_line()),
+            }
+        }
+
+        report
+    }
+
+    #[test]
+    fn counts() {
+        let report = report();
+        assert_eq!(report.valid, 1);
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.skipped, vec![
+            SkippedRecord { record: 2, first_line: 4, last_line: 4 },
+            SkippedRecord { record: 4, first_line: 8, last_line: 9 },
+        ]);
+        let failures_per_field: Vec<(&str, usize)> = report.failures_per_field.iter()
+            .map(|(field, count)| (field.as_str(), *count))
+            .collect();
+        assert_eq!(failures_per_field, vec![("ecl", 1), ("eyr", 1), ("hcl", 1), ("pid", 1)]);
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(report().to_string(), "\
+record 1 (lines 1-2):
+  eyr: required field is missing
+  hcl: required field is missing
+  ecl: required field is missing
+  2:5: pid: does not match ^[0-9]{9}$ (found '1')
+record 2 (lines 4-4): skipped, a field is malformed
+record 4 (lines 8-9): skipped, a field is malformed
+failures per field:
+  ecl    1
+  eyr    1
+  hcl    1
+  pid    1
+valid: 1, invalid: 1, skipped: 2
+");
+    }
+
+    #[test]
+    fn json() {
+        let missing = |field: &str| serde_json::json!({
+            "field": field,
+            "value": null,
+            "line": null,
+            "column": null,
+            "violation": "required field is missing",
+        });
+
+        assert_eq!(serde_json::to_value(report()).unwrap(), serde_json::json!({
+            "valid": 1,
+            "invalid": [{
+                "record": 1,
+                "first_line": 1,
+                "last_line": 2,
+                "failures": [
+                    missing("eyr"),
+                    missing("hcl"),
+                    missing("ecl"),
+                    {
+                        "field": "pid",
+                        "value": "1",
+                        "line": 2,
+                        "column": 5,
+                        "violation": "does not match ^[0-9]{9}$",
+                    },
+                ],
+            }],
+            "skipped": [
+                { "record": 2, "first_line": 4, "last_line": 4 },
+                { "record": 4, "first_line": 8, "last_line": 9 },
+            ],
+            "failures_per_field": { "ecl": 1, "eyr": 1, "hcl": 1, "pid": 1 },
+        }));
+    }
+}
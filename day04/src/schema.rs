@@ -0,0 +1,390 @@
+//! Declarative passport validation rules, loaded from a TOML or JSON
+//! schema. See schema.toml for the format, and for the rules of the
+//! puzzle which are used by default.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize, Serializer};
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use common::{Input, Result};
+
+use crate::Passport;
+
+/// The rules of the puzzle.
+pub const DEFAULT_SCHEMA: &str = include_str!("../schema.toml");
+
+lazy_static! {
+    static ref DEFAULT: Schema = Schema::parse(
+        &Input::from_string("<default schema>", DEFAULT_SCHEMA.to_string()))
+        .expect("the default schema is valid");
+}
+
+/// The type of the value of a field.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Type {
+    #[default]
+    String,
+    Integer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    name: String,
+    #[serde(default)]
+    required: bool,
+    #[serde(default, rename = "type")]
+    kind: Type,
+    range: Option<(i64, i64)>,
+    units: Option<BTreeMap<String, (i64, i64)>>,
+    regex: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSchema {
+    #[serde(default)]
+    field: Vec<RawRule>,
+}
+
+/// The rule a field must satisfy.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    pub name: String,
+    pub required: bool,
+    pub kind: Type,
+    pub range: Option<(i64, i64)>,
+    /// The accepted units, suffixing the value, and their own range.
+    pub units: BTreeMap<String, (i64, i64)>,
+    /// The pattern of the values as written in the schema, and the
+    /// regex it compiles to, which only matches whole values.
+    pub pattern: Option<String>,
+    pub regex: Option<Regex>,
+}
+
+/// The reason why a value does not satisfy a rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    Missing,
+    NotInteger,
+    OutOfRange(i64, i64),
+    MissingUnit,
+    NoMatch(String),
+}
+
+impl Serialize for Violation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A field of a passport not satisfying its rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    pub field: String,
+    /// The value of the field, and its line and column in the input,
+    /// unless the field is missing.
+    pub value: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub violation: Violation,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Missing =>
+                write!(f, "required field is missing"),
+            Violation::NotInteger =>
+                write!(f, "not an integer"),
+            Violation::OutOfRange(min, max) =>
+                write!(f, "not within {}..={}", min, max),
+            Violation::MissingUnit =>
+                write!(f, "missing or unknown unit"),
+            Violation::NoMatch(regex) =>
+                write!(f, "does not match {}", regex),
+        }
+    }
+}
+
+/// Checks that |val| is within |range|, if any.
+fn check_range(val: i64, range: Option<(i64, i64)>) -> std::result::Result<(), Violation> {
+    match range {
+        Some((min, max)) if val < min || val > max =>
+            Err(Violation::OutOfRange(min, max)),
+        _ =>
+            Ok(()),
+    }
+}
+
+impl Rule {
+    /// Checks |value| against the rule.
+    pub fn check(&self, value: &str) -> std::result::Result<(), Violation> {
+        if let (Some(pattern), Some(regex)) = (&self.pattern, &self.regex) {
+            if !regex.is_match(value) {
+                return Err(Violation::NoMatch(pattern.clone()));
+            }
+        }
+
+        let (value, range) = if self.units.is_empty() {
+            (value, self.range)
+        } else {
+            // Units can suffix one another, e.g. "m" and "cm": the
+            // longest one wins.
+            let (unit, range) = self.units.iter()
+                .filter(|(unit, _)| value.ends_with(unit.as_str()))
+                .max_by_key(|(unit, _)| unit.len())
+                .ok_or(Violation::MissingUnit)?;
+            (&value[..value.len() - unit.len()], Some(*range))
+        };
+
+        if self.kind == Type::Integer {
+            let val = value.parse::<i64>()
+                .map_err(|_| Violation::NotInteger)?;
+            check_range(val, range)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A set of rules, one per field.
+#[derive(Clone, Debug)]
+pub struct Schema {
+    pub rules: Vec<Rule>,
+}
+
+impl Schema {
+    /// Parses a schema, in JSON if the path of |input| ends with
+    /// ".json" and in TOML otherwise.
+    pub fn parse(input: &Input) -> Result<Schema> {
+        let text = input.text();
+        let raw: RawSchema = if input.path().ends_with(".json") {
+            serde_json::from_str(text)
+                .map_err(|e| {
+                    // The message ends with the location, reported apart.
+                    let message = e.to_string();
+                    let message = message.rsplit_once(" at line ").map_or(&message[..], |(m, _)| m);
+                    input.error(e.line(), e.column(), "", message)
+                })?
+        } else {
+            toml::from_str(text)
+                .map_err(|e| input.error_at_offset(e.span().map_or(0, |span| span.start), e.message()))?
+        };
+
+        for (i, rule) in raw.field.iter().enumerate() {
+            if raw.field[..i].iter().any(|other| other.name == rule.name) {
+                let line = Schema::lines_naming(input, &rule.name).nth(1).unwrap_or(1);
+                return Err(input.error(line, 1, rule.name.as_str(), "field has several rules"));
+            }
+        }
+
+        let rules = raw.field.into_iter()
+            .map(|rule| Schema::build_rule(input, rule))
+            .collect::<Result<Vec<Rule>>>()?;
+
+        Ok(Schema { rules })
+    }
+
+    /// Returns the rules of the puzzle.
+    pub fn default_schema() -> &'static Schema {
+        &DEFAULT
+    }
+
+    /// Returns the number of every line of |input| naming the field
+    /// |name|. The schema formats do not keep track of where values
+    /// come from, so errors point to these lines.
+    fn lines_naming<'a>(input: &'a Input, name: &str) -> impl Iterator<Item = usize> + 'a {
+        let quoted = format!("\"{}\"", name);
+        input.lines()
+            .filter(move |line| line.text.contains(&quoted))
+            .map(|line| line.number)
+    }
+
+    /// Checks the consistency of a rule, and compiles its regex.
+    fn build_rule(input: &Input, raw: RawRule) -> Result<Rule> {
+        let line = Schema::lines_naming(input, &raw.name).next().unwrap_or(1);
+        let error = |message: &str| input.error(line, 1, raw.name.as_str(), message);
+
+        if raw.kind != Type::Integer && (raw.range.is_some() || raw.units.is_some()) {
+            return Err(error("ranges and units only apply to integer fields"));
+        }
+        if raw.range.is_some() && raw.units.is_some() {
+            return Err(error("the range of a field with units is given per unit"));
+        }
+        let mut ranges = raw.range.iter().chain(raw.units.iter().flat_map(|units| units.values()));
+        if ranges.any(|(min, max)| min > max) {
+            return Err(error("range is empty"));
+        }
+
+        let regex = match &raw.regex {
+            // The pattern is compiled on its own first, so that errors
+            // point into it rather than into its anchored form.
+            Some(regex) => Some(Regex::new(regex)
+                .and_then(|_| Regex::new(&format!("^(?:{})$", regex)))
+                .map_err(|e| error(&format!("invalid regex: {}", e)))?),
+            None => None,
+        };
+
+        Ok(Rule {
+            name: raw.name,
+            required: raw.required,
+            kind: raw.kind,
+            range: raw.range,
+            units: raw.units.unwrap_or_default(),
+            pattern: raw.regex,
+            regex,
+        })
+    }
+
+    /// Returns the rule of the field named |name|, if any.
+    pub fn rule(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Returns whether |passport| has every required field.
+    pub fn is_complete(&self, passport: &Passport) -> bool {
+        self.rules.iter()
+            .filter(|rule| rule.required)
+            .all(|rule| passport.get(&rule.name).is_some())
+    }
+
+    /// Returns whether |passport| has every required field, and whether
+    /// each of its fields satisfies its rule.
+    pub fn is_valid(&self, passport: &Passport) -> bool {
+        self.validate(passport).is_empty()
+    }
+
+    /// Returns every field of |passport| that is missing or does not
+    /// satisfy its rule, in the order of the rules.
+    pub fn validate(&self, passport: &Passport) -> Vec<Failure> {
+        let mut failures = Vec::new();
+
+        for rule in &self.rules {
+            let violation = match passport.field(&rule.name) {
+                Some(field) => rule.check(&field.value).err(),
+                None if rule.required => Some(Violation::Missing),
+                None => None,
+            };
+
+            if let Some(violation) = violation {
+                let field = passport.field(&rule.name);
+                failures.push(Failure {
+                    field: rule.name.clone(),
+                    value: field.map(|field| field.value.clone()),
+                    line: field.map(|field| field.line),
+                    column: field.map(|field| field.column),
+                    violation,
+                });
+            }
+        }
+
+        failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(text: &str) -> Schema {
+        Schema::parse(&Input::from_string("schema.toml", text.to_string())).unwrap()
+    }
+
+    #[test]
+    fn default_rules() {
+        let schema = Schema::default_schema();
+        let check = |name: &str, value: &str| schema.rule(name).unwrap().check(value);
+
+        assert_eq!(check("byr", "2002"), Ok(()));
+        assert_eq!(check("byr", "1919"), Err(Violation::OutOfRange(1920, 2020)));
+        assert_eq!(check("hgt", "60in"), Ok(()));
+        assert_eq!(check("hgt", "190cm"), Ok(()));
+        assert_eq!(check("hgt", "190in"), Err(Violation::OutOfRange(59, 76)));
+        assert_eq!(check("hgt", "190"), Err(Violation::MissingUnit));
+        assert_eq!(check("hcl", "#123abc"), Ok(()));
+        assert_eq!(check("hcl", "#123abz"), Err(Violation::NoMatch("^#[0-9a-f]{6}$".to_string())));
+        assert_eq!(check("ecl", "brn"), Ok(()));
+        assert_eq!(check("ecl", "wat"), Err(Violation::NoMatch("^(amb|blu|brn|gry|grn|hzl|oth)$".to_string())));
+        assert_eq!(check("pid", "000000001"), Ok(()));
+        assert_eq!(check("pid", "0123456789"), Err(Violation::NoMatch("^[0-9]{9}$".to_string())));
+    }
+
+    #[test]
+    fn regex_matches_whole_values() {
+        let schema = schema("[[field]]\nname = \"pid\"\nregex = \"[0-9]{9}|x\"\n");
+        let rule = schema.rule("pid").unwrap();
+
+        assert_eq!(rule.check("012345678"), Ok(()));
+        assert_eq!(rule.check("x"), Ok(()));
+        assert_eq!(rule.check("0123456789"), Err(Violation::NoMatch("[0-9]{9}|x".to_string())));
+        assert_eq!(rule.check("a012345678"), Err(Violation::NoMatch("[0-9]{9}|x".to_string())));
+        assert_eq!(rule.check("xx"), Err(Violation::NoMatch("[0-9]{9}|x".to_string())));
+    }
+
+    #[test]
+    fn longest_unit() {
+        let schema = schema("[[field]]\nname = \"hgt\"\ntype = \"integer\"\nunits = { m = [1, 2], cm = [150, 193] }\n");
+        let rule = schema.rule("hgt").unwrap();
+
+        assert_eq!(rule.check("2m"), Ok(()));
+        assert_eq!(rule.check("160cm"), Ok(()));
+        assert_eq!(rule.check("3m"), Err(Violation::OutOfRange(1, 2)));
+        assert_eq!(rule.check("2cm"), Err(Violation::OutOfRange(150, 193)));
+        assert_eq!(rule.check("160"), Err(Violation::MissingUnit));
+    }
+
+    #[test]
+    fn invalid_regex() {
+        let error = Schema::parse(&Input::from_string("schema.toml",
+            "[[field]]\nname = \"pid\"\nregex = \"(a\"\n".to_string())).unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("schema.toml:2:1: invalid regex"), "{}", message);
+        assert!(!message.contains("(?:"), "{}", message);
+    }
+
+    #[test]
+    fn invalid_toml() {
+        let error = Schema::parse(&Input::from_string("schema.toml",
+            "[[field]]\nname = \"pid\"\nrequired = yes\n".to_string())).unwrap_err();
+        assert_eq!(error.to_string(), "schema.toml:3:12: invalid string\nexpected `\"`, `'` (found 'required = yes')");
+    }
+
+    #[test]
+    fn json() {
+        let schema = Schema::parse(&Input::from_string("schema.json", r#"{
+            "field": [
+                { "name": "byr", "required": true, "type": "integer", "range": [1920, 2002] },
+                { "name": "cid" }
+            ]
+        }"#.to_string())).unwrap();
+        assert_eq!(schema.rules.len(), 2);
+        assert_eq!(schema.rule("byr").unwrap().check("1919"), Err(Violation::OutOfRange(1920, 2002)));
+        assert!(!schema.rule("cid").unwrap().required);
+    }
+
+    #[test]
+    fn invalid_json() {
+        let parse = |text: &str| Schema::parse(&Input::from_string("schema.json", text.to_string()))
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(parse("{\n  \"field\": [\n    { \"name\": \"byr\", }\n  ]\n}"),
+                   "schema.json:3:22: trailing comma");
+        assert_eq!(parse("{ \"field\": [{ \"name\": \"byr\", \"min\": 1 }] }"),
+                   "schema.json:1:34: unknown field `min`, expected one of `name`, `required`, `type`, `range`, `units`, `regex`");
+        assert_eq!(parse("{ \"field\": [\n"), "schema.json:2:0: EOF while parsing a list");
+    }
+
+    #[test]
+    fn duplicate_fields() {
+        let error = Schema::parse(&Input::from_string("schema.toml",
+            "[[field]]\nname = \"pid\"\n\n[[field]]\nname = \"byr\"\n\n[[field]]\nname = \"pid\"\n".to_string()))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "schema.toml:8:1: field has several rules (found 'pid')");
+    }
+}
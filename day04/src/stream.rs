@@ -0,0 +1,261 @@
+//! Validation of batch files too large to be loaded whole.
+//!
+//! Records are read one at a time from a RecordReader, and validated in
+//! batches, optionally spread across threads. Memory use is bounded by
+//! the size of a batch, and records come out in their input order.
+
+use std::collections::VecDeque;
+use std::thread;
+
+use common::input::RecordBuf;
+use common::Result;
+
+use crate::parser::{Diagnostic, Parser};
+use crate::schema::Failure;
+use crate::Passport;
+
+/// Number of records validated at once by each thread.
+const RECORDS_PER_THREAD: usize = 256;
+
+/// A record, parsed and validated.
+#[derive(Debug)]
+pub struct Checked {
+    /// The 1-based index of the record in the input, and the lines it
+    /// spans.
+    pub record: usize,
+    pub first_line: usize,
+    pub last_line: usize,
+    /// The passport of the record, unless it was skipped.
+    pub passport: Option<Passport>,
+    pub diagnostics: Vec<Diagnostic>,
+    pub complete: bool,
+    pub failures: Vec<Failure>,
+}
+
+impl Checked {
+    fn new(parser: &Parser, record: usize, buf: &RecordBuf) -> Checked {
+        let mut diagnostics = Vec::new();
+        let lines = buf.record();
+        let passport = parser.parse_record(record, &lines, &mut diagnostics);
+        let schema = parser.schema();
+        let (complete, failures) = match &passport {
+            Some(passport) => (schema.is_complete(passport), schema.validate(passport)),
+            None => (false, Vec::new()),
+        };
+
+        Checked {
+            record,
+            first_line: lines.first_line(),
+            last_line: lines.last_line(),
+            passport,
+            diagnostics,
+            complete,
+            failures,
+        }
+    }
+
+    /// Returns whether the record is a valid passport.
+    pub fn is_valid(&self) -> bool {
+        self.passport.is_some() && self.failures.is_empty()
+    }
+}
+
+/// Iterator over the records of a stream, parsed and validated, see
+/// check_records().
+pub struct CheckedRecords<'a, I> {
+    parser: &'a Parser<'a>,
+    records: I,
+    threads: usize,
+    /// Number of records read so far.
+    count: usize,
+    pending: VecDeque<Result<Checked>>,
+}
+
+/// Parses and validates every record of |records| with |parser|, using
+/// up to |threads| threads.
+pub fn check_records<'a, I>(parser: &'a Parser<'a>, records: I, threads: usize) -> CheckedRecords<'a, I>
+where
+    I: Iterator<Item = Result<RecordBuf>>,
+{
+    CheckedRecords {
+        parser,
+        records,
+        threads: threads.max(1),
+        count: 0,
+        pending: VecDeque::new(),
+    }
+}
+
+impl<'a, I> CheckedRecords<'a, I>
+where
+    I: Iterator<Item = Result<RecordBuf>>,
+{
+    /// Reads and validates the next batch of records.
+    fn fill(&mut self) {
+        let mut batch = Vec::new();
+        let mut error = None;
+
+        for res in self.records.by_ref().take(self.threads * RECORDS_PER_THREAD) {
+            match res {
+                Ok(buf) => {
+                    self.count += 1;
+                    batch.push((self.count, buf));
+                },
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                },
+            }
+        }
+
+        let parser = self.parser;
+        if self.threads == 1 {
+            self.pending.extend(batch.iter().map(|(i, buf)| Ok(Checked::new(parser, *i, buf))));
+        } else {
+            let chunks: Vec<Vec<Checked>> = thread::scope(|scope| {
+                let handles: Vec<_> = batch.chunks(RECORDS_PER_THREAD)
+                    .map(|chunk| scope.spawn(move || {
+                        chunk.iter()
+                            .map(|(i, buf)| Checked::new(parser, *i, buf))
+                            .collect::<Vec<Checked>>()
+                    }))
+                    .collect();
+                handles.into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                    .collect()
+            });
+            self.pending.extend(chunks.into_iter().flatten().map(Ok));
+        }
+
+        if let Some(e) = error {
+            self.pending.push_back(Err(e));
+        }
+    }
+}
+
+impl<'a, I> Iterator for CheckedRecords<'a, I>
+where
+    I: Iterator<Item = Result<RecordBuf>>,
+{
+    type Item = Result<Checked>;
+
+    fn next(&mut self) -> Option<Result<Checked>> {
+        if self.pending.is_empty() {
+            self.fill();
+        }
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::input::RecordReader;
+    use common::Error;
+
+    use std::io;
+
+    use crate::schema::Schema;
+    use crate::Mode;
+
+    /// A batch file of |n| records, cycling through valid, invalid,
+    /// incomplete and malformed passports of one or two lines.
+    fn batch_file(n: usize) -> String {
+        let mut text = String::new();
+        for i in 0..n {
+            let record = match i % 4 {
+                0 => format!("pid:{:09} hcl:#123abc ecl:brn\nhgt:{}cm byr:1980 iyr:2012 eyr:2025\n", i, 150 + i % 50),
+                1 => format!("pid:{} hcl:#123abc ecl:wat hgt:{}in byr:1980 iyr:2012 eyr:2025\n", i, 50 + i % 30),
+                2 => format!("pid:{:09} byr:{}\n", i, 1900 + i % 150),
+                _ => format!("pid:{:09} byr:{} junk\n", i, 1980),
+            };
+            text.push_str(&record);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The outcome of checking a record, comparable across runs.
+    type Summary = (usize, Option<String>, bool, Vec<Failure>, Vec<Diagnostic>);
+
+    fn summarize(checked: Checked) -> Summary {
+        let pid = checked.passport.as_ref().and_then(|passport| passport.get("pid").map(str::to_string));
+        (checked.record, pid, checked.complete, checked.failures, checked.diagnostics)
+    }
+
+    fn check(text: &str, mode: Mode, threads: usize) -> Vec<Summary> {
+        let schema = Schema::default_schema();
+        let parser = Parser::new(schema, mode);
+        let records = RecordReader::new("batch", io::Cursor::new(text.as_bytes()));
+        check_records(&parser, records, threads)
+            .map(|res| summarize(res.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn threads_keep_order() {
+        // Several batches of 4 threads, the last one partial.
+        let text = batch_file(4 * RECORDS_PER_THREAD * 2 + 77);
+        for mode in [Mode::Lenient, Mode::Strict].iter() {
+            let sequential = check(&text, *mode, 1);
+            assert_eq!(sequential.len(), 4 * RECORDS_PER_THREAD * 2 + 77);
+            assert!(sequential.iter().enumerate().all(|(i, summary)| summary.0 == i + 1));
+            assert_eq!(check(&text, *mode, 4), sequential);
+            assert_eq!(check(&text, *mode, 3), sequential);
+        }
+    }
+
+    #[test]
+    fn matches_whole_input() {
+        let text = batch_file(300);
+        let schema = Schema::default_schema();
+        let parser = Parser::new(schema, Mode::Strict);
+        let batch = parser.parse(&common::Input::from_string("batch", text.clone()));
+
+        let checked = check(&text, Mode::Strict, 2);
+        let diagnostics: Vec<Diagnostic> = checked.iter().flat_map(|summary| summary.4.clone()).collect();
+        assert_eq!(diagnostics, batch.diagnostics);
+        let complete = checked.iter().filter(|summary| summary.2).count();
+        assert_eq!(complete, batch.passports.iter().filter(|passport| schema.is_complete(passport)).count());
+    }
+
+    #[test]
+    fn errors_in_order() {
+        let buf = |i: usize| RecordBuf {
+            first_line: 2 * i + 1,
+            texts: vec![format!("pid:{:09}", i)],
+        };
+        let error = || Error::Io {
+            path: "batch".to_string(),
+            source: io::Error::other("disk on fire"),
+        };
+        let n = RECORDS_PER_THREAD * 2 + 10;
+        let records: Vec<Result<RecordBuf>> = (0..n).map(|i| Ok(buf(i)))
+            .chain(std::iter::once(Err(error())))
+            .chain((n..n + 5).map(|i| Ok(buf(i))))
+            .collect();
+
+        for threads in [1, 2, 4].iter() {
+            let parser = Parser::new(Schema::default_schema(), Mode::Lenient);
+            let results: Vec<Result<Checked>> = check_records(&parser, records.iter().map(|res| match res {
+                Ok(buf) => Ok(buf.clone()),
+                Err(_) => Err(error()),
+            }), *threads).collect();
+
+            assert_eq!(results.len(), n + 6);
+            for (i, res) in results.iter().enumerate() {
+                match res {
+                    Ok(checked) if i < n =>
+                        assert_eq!(checked.record, i + 1),
+                    Err(e) if i == n =>
+                        assert_eq!(e.to_string(), "batch: disk on fire"),
+                    Ok(checked) if i > n =>
+                        assert_eq!(checked.record, i),
+                    _ =>
+                        panic!("unexpected result {} with {} threads", i, threads),
+                }
+            }
+        }
+    }
+}
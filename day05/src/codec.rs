@@ -0,0 +1,346 @@
+//! Boarding passes, as binary space partitions of the seats of a plane.
+//!
+//! A pass first partitions the rows, one character per halving, then
+//! the columns of the remaining row. Each character picks the lower or
+//! the upper half, so a pass is the binary writing of the id of its
+//! seat, row * cols + col, with its own pair of digits for each part.
+
+use std::error;
+use std::fmt;
+
+use common::{Error, Result};
+
+use crate::Seat;
+
+/// The part of a pass a character belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    Row,
+    Column,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::Row => write!(f, "row"),
+            Part::Column => write!(f, "column"),
+        }
+    }
+}
+
+/// The reasons why a pass cannot be decoded, or a seat encoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PassError {
+    /// The pass does not have one character per halving.
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    /// The character at the 0-based |index| of the pass is not one of
+    /// the letters of its part.
+    Letter {
+        index: usize,
+        found: char,
+        part: Part,
+        expected: [char; 2],
+    },
+    /// The seat is not on the plane.
+    OutOfRange {
+        row: usize,
+        col: usize,
+    },
+}
+
+impl PassError {
+    /// Converts the error into a parse error on |pass|, yet to be
+    /// located.
+    pub fn at(self, pass: &str) -> Error {
+        match &self {
+            PassError::Letter { index, found, .. } =>
+                Error::parse(index + 1, found.to_string(), self),
+            _ =>
+                Error::parse(1, pass, self),
+        }
+    }
+}
+
+impl fmt::Display for PassError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PassError::Length { expected, found } =>
+                write!(f, "boarding pass must have {} characters, not {}", expected, found),
+            PassError::Letter { part, expected, .. } =>
+                write!(f, "invalid {} character, expected '{}' or '{}'", part, expected[0], expected[1]),
+            PassError::OutOfRange { row, col } =>
+                write!(f, "seat at row {}, column {} is not on the plane", row, col),
+        }
+    }
+}
+
+impl error::Error for PassError {}
+
+/// Encodes and decodes the passes of a plane.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Codec {
+    row_bits: usize,
+    col_bits: usize,
+    /// The letters of the lower and upper halves, for each part.
+    row_letters: [char; 2],
+    col_letters: [char; 2],
+    /// Whether letters are decoded regardless of their case, so that
+    /// distinct passes can decode to the same seat.
+    ignore_case: bool,
+}
+
+impl Default for Codec {
+    /// The codec of the puzzle: 128 rows of 8 seats, and passes such
+    /// as "FBFBBFFRLR".
+    fn default() -> Codec {
+        Codec {
+            row_bits: 7,
+            col_bits: 3,
+            row_letters: ['F', 'B'],
+            col_letters: ['L', 'R'],
+            ignore_case: false,
+        }
+    }
+}
+
+/// Returns log2(|n|), if |n| is a power of two.
+fn log2(n: usize, what: &str) -> Result<usize> {
+    if n.is_power_of_two() {
+        Ok(n.trailing_zeros() as usize)
+    } else {
+        Err(Error::Unsupported(format!("the number of {} must be a power of two, not {}", what, n)))
+    }
+}
+
+impl Codec {
+    /// Creates the codec of a plane of |rows| rows of |cols| seats,
+    /// both powers of two, the letters picking the lower and upper
+    /// halves of each part being given in that order.
+    pub fn new(rows: usize, cols: usize, row_letters: [char; 2], col_letters: [char; 2]) -> Result<Codec> {
+        let codec = Codec {
+            row_bits: log2(rows, "rows")?,
+            col_bits: log2(cols, "columns")?,
+            row_letters,
+            col_letters,
+            ignore_case: false,
+        };
+
+        if codec.row_bits + codec.col_bits >= usize::BITS as usize {
+            return Err(Error::Unsupported(format!("a plane of {} x {} seats is too large", rows, cols)));
+        }
+        if row_letters[0] == row_letters[1] || col_letters[0] == col_letters[1] {
+            return Err(Error::Unsupported("the letters of the halves must differ".to_string()));
+        }
+
+        Ok(codec)
+    }
+
+    /// Makes the codec decode letters regardless of their case. Passes
+    /// are still encoded with the letters given to new().
+    pub fn ignoring_case(self) -> Codec {
+        Codec {
+            ignore_case: true,
+            ..self
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn cols(&self) -> usize {
+        1 << self.col_bits
+    }
+
+    /// Returns the number of seats of the plane, which is also one more
+    /// than the largest id.
+    pub fn num_seats(&self) -> usize {
+        1 << (self.row_bits + self.col_bits)
+    }
+
+    /// Returns the number of characters of a pass.
+    pub fn pass_len(&self) -> usize {
+        self.row_bits + self.col_bits
+    }
+
+    /// Returns the id of |seat|, i.e. its index on the plane.
+    pub fn id(&self, seat: &Seat) -> usize {
+        seat.row() << self.col_bits | seat.col()
+    }
+
+    /// Returns the seat of |id|.
+    pub fn seat(&self, id: usize) -> Seat {
+        Seat::from(id >> self.col_bits, id & (self.cols() - 1))
+    }
+
+    /// Decodes |pass| into the id of its seat.
+    pub fn decode_id(&self, pass: &str) -> std::result::Result<usize, PassError> {
+        let len = pass.chars().count();
+        if len != self.pass_len() {
+            return Err(PassError::Length {
+                expected: self.pass_len(),
+                found: len,
+            });
+        }
+
+        let mut id = 0;
+        for (index, c) in pass.chars().enumerate() {
+            let (part, letters) = if index < self.row_bits {
+                (Part::Row, self.row_letters)
+            } else {
+                (Part::Column, self.col_letters)
+            };
+
+            let bit = letters.iter()
+                .position(|letter| *letter == c)
+                .or_else(|| letters.iter()
+                    .position(|letter| self.ignore_case && letter.eq_ignore_ascii_case(&c)))
+                .ok_or(PassError::Letter {
+                    index,
+                    found: c,
+                    part,
+                    expected: letters,
+                })?;
+            id = id << 1 | bit;
+        }
+
+        Ok(id)
+    }
+
+    /// Decodes |pass| into its seat.
+    pub fn decode(&self, pass: &str) -> std::result::Result<Seat, PassError> {
+        self.decode_id(pass).map(|id| self.seat(id))
+    }
+
+    /// Encodes |seat| into its pass.
+    pub fn encode(&self, seat: &Seat) -> std::result::Result<String, PassError> {
+        if seat.row() >= self.rows() || seat.col() >= self.cols() {
+            return Err(PassError::OutOfRange {
+                row: seat.row(),
+                col: seat.col(),
+            });
+        }
+
+        let id = self.id(seat);
+        let pass = (0..self.pass_len())
+            .map(|index| {
+                let letters = if index < self.row_bits { self.row_letters } else { self.col_letters };
+                letters[id >> (self.pass_len() - 1 - index) & 1]
+            })
+            .collect();
+
+        Ok(pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize, usize); 4] = [
+        ("FBFBBFFRLR", 44, 5, 357),
+        ("BFFFBBFRRR", 70, 7, 567),
+        ("FFFBBBFRRR", 14, 7, 119),
+        ("BBFFBBFRLL", 102, 4, 820),
+    ];
+
+    #[test]
+    fn decode_examples() {
+        let codec = Codec::default();
+        for (pass, row, col, id) in EXAMPLES.iter() {
+            assert_eq!(codec.decode(pass), Ok(Seat::from(*row, *col)));
+            assert_eq!(codec.decode_id(pass), Ok(*id));
+            assert_eq!(codec.id(&Seat::from(*row, *col)), *id);
+        }
+    }
+
+    #[test]
+    fn encode_examples() {
+        let codec = Codec::default();
+        for (pass, row, col, _) in EXAMPLES.iter() {
+            assert_eq!(codec.encode(&Seat::from(*row, *col)).as_deref(), Ok(*pass));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let codec = Codec::new(16, 4, ['U', 'D'], ['<', '>']).unwrap();
+        assert_eq!(codec.pass_len(), 6);
+        assert_eq!(codec.num_seats(), 64);
+        for id in 0..codec.num_seats() {
+            let seat = codec.seat(id);
+            assert_eq!(codec.id(&seat), id);
+            let pass = codec.encode(&seat).unwrap();
+            assert_eq!(codec.decode_id(&pass), Ok(id));
+        }
+        assert_eq!(codec.encode(&Seat::from(9, 2)).as_deref(), Ok("DUUD><"));
+    }
+
+    #[test]
+    fn ignore_case() {
+        let codec = Codec::default();
+        assert!(codec.decode_id("fbFBBFFRLR").is_err());
+
+        let codec = codec.ignoring_case();
+        assert_eq!(codec.decode_id("fbFBBFFrLr"), Ok(357));
+        assert_eq!(codec.encode(&Seat::from(44, 5)).as_deref(), Ok("FBFBBFFRLR"));
+    }
+
+    #[test]
+    fn malformed_passes() {
+        let codec = Codec::default();
+        assert_eq!(codec.decode_id(""), Err(PassError::Length { expected: 10, found: 0 }));
+        assert_eq!(codec.decode_id("FBFBBFFRL"), Err(PassError::Length { expected: 10, found: 9 }));
+        assert_eq!(codec.decode_id("FBFBBFFRLRL"), Err(PassError::Length { expected: 10, found: 11 }));
+        // Lengths are counted in characters, not bytes.
+        assert_eq!(codec.decode_id("FBFBBFFRLé"), Err(PassError::Letter {
+            index: 9,
+            found: 'é',
+            part: Part::Column,
+            expected: ['L', 'R'],
+        }));
+        assert_eq!(codec.decode_id("FBFLBFFRLR"), Err(PassError::Letter {
+            index: 3,
+            found: 'L',
+            part: Part::Row,
+            expected: ['F', 'B'],
+        }));
+        assert_eq!(codec.decode_id("FBFBBFFRBR"), Err(PassError::Letter {
+            index: 8,
+            found: 'B',
+            part: Part::Column,
+            expected: ['L', 'R'],
+        }));
+    }
+
+    #[test]
+    fn located_errors() {
+        let error = Codec::default().decode_id("FBFLBFFRLR").unwrap_err();
+        assert_eq!(error.at("FBFLBFFRLR").to_string(),
+            Error::parse(4, "L", "invalid row character, expected 'F' or 'B'").to_string());
+
+        let error = Codec::default().decode_id("FB").unwrap_err();
+        assert_eq!(error.at("FB").to_string(),
+            Error::parse(1, "FB", "boarding pass must have 10 characters, not 2").to_string());
+    }
+
+    #[test]
+    fn out_of_range_seats() {
+        let codec = Codec::default();
+        assert_eq!(codec.encode(&Seat::from(128, 0)), Err(PassError::OutOfRange { row: 128, col: 0 }));
+        assert_eq!(codec.encode(&Seat::from(0, 8)), Err(PassError::OutOfRange { row: 0, col: 8 }));
+        assert_eq!(codec.encode(&Seat::from(127, 7)).as_deref(), Ok("BBBBBBBRRR"));
+    }
+
+    #[test]
+    fn invalid_codecs() {
+        assert!(matches!(Codec::new(100, 8, ['F', 'B'], ['L', 'R']), Err(Error::Unsupported(_))));
+        assert!(matches!(Codec::new(128, 0, ['F', 'B'], ['L', 'R']), Err(Error::Unsupported(_))));
+        assert!(matches!(Codec::new(128, 8, ['F', 'F'], ['L', 'R']), Err(Error::Unsupported(_))));
+        assert!(matches!(Codec::new(1 << 40, 1 << 30, ['F', 'B'], ['L', 'R']), Err(Error::Unsupported(_))));
+        assert_eq!(Codec::new(128, 8, ['F', 'B'], ['L', 'R']).unwrap(), Codec::default());
+    }
+}
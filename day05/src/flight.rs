@@ -0,0 +1,329 @@
+//! The boarding passes of a whole flight: seat map and statistics.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+
+use common::{Input, Result};
+
+use crate::{find_gaps, Codec, Seat};
+
+/// A boarding pass, and the seat it decodes to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pass {
+    pub line: usize,
+    pub text: String,
+    pub id: usize,
+}
+
+/// What is wrong with a boarding pass.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// The same pass was scanned on an earlier line.
+    Duplicate(usize),
+    /// A different pass of the same seat was scanned on an earlier line.
+    Conflict(usize, String),
+    /// The seat is not in the rows of the plane that exist.
+    OutOfRange(usize),
+}
+
+/// An issue found with the boarding pass on a line of the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub text: String,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::Duplicate(line) =>
+                write!(f, "duplicate pass, first scanned on line {}", line),
+            Issue::Conflict(line, text) =>
+                write!(f, "seat already taken by pass '{}' on line {}", text, line),
+            Issue::OutOfRange(row) =>
+                write!(f, "seat is on row {}, which does not exist", row),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} (found '{}')", self.line, self.issue, self.text)
+    }
+}
+
+/// The boarding passes scanned for a flight.
+#[derive(Clone, Debug)]
+pub struct Flight {
+    pub codec: Codec,
+    pub passes: Vec<Pass>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The number of passes of each taken seat, by id.
+    taken: HashMap<usize, usize>,
+}
+
+/// Statistics about the seats taken on a flight.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub num_passes: usize,
+    pub num_taken: usize,
+    /// The number of seats taken in each row.
+    pub taken_per_row: Vec<usize>,
+    pub first_row: Option<usize>,
+    pub last_row: Option<usize>,
+    /// The passes scanned more than once, and the lines they are on.
+    pub duplicates: BTreeMap<String, Vec<usize>>,
+}
+
+impl Flight {
+    /// Decodes the boarding passes of |input|, one per line. Passes
+    /// scanned twice, passes of the same seat, and seats out of |rows|
+    /// when given, are diagnosed but kept.
+    pub fn from_input(input: &Input, codec: &Codec, rows: Option<RangeInclusive<usize>>) -> Result<Flight> {
+        let mut flight = Flight {
+            codec: codec.clone(),
+            passes: Vec::new(),
+            diagnostics: Vec::new(),
+            taken: HashMap::new(),
+        };
+        // The index of the first pass of each taken seat.
+        let mut first_pass: HashMap<usize, usize> = HashMap::new();
+
+        for line in input.lines().filter(|line| !line.text.is_empty()) {
+            let id = codec.decode_id(line.text)
+                .map_err(|e| input.locate(e.at(line.text), line.number))?;
+
+            let mut issues = Vec::new();
+            if let Some(first) = first_pass.get(&id).map(|i| &flight.passes[*i]) {
+                if first.text == line.text {
+                    issues.push(Issue::Duplicate(first.line));
+                } else {
+                    issues.push(Issue::Conflict(first.line, first.text.clone()));
+                }
+            }
+            let row = codec.seat(id).row();
+            if rows.as_ref().is_some_and(|rows| !rows.contains(&row)) {
+                issues.push(Issue::OutOfRange(row));
+            }
+            flight.diagnostics.extend(issues.into_iter().map(|issue| Diagnostic {
+                line: line.number,
+                text: line.text.to_string(),
+                issue,
+            }));
+
+            first_pass.entry(id).or_insert(flight.passes.len());
+            flight.passes.push(Pass {
+                line: line.number,
+                text: line.text.to_string(),
+                id,
+            });
+            *flight.taken.entry(id).or_insert(0) += 1;
+        }
+
+        Ok(flight)
+    }
+
+    /// Returns the number of passes of the seat |id|.
+    pub fn num_passes(&self, id: usize) -> usize {
+        self.taken.get(&id).copied().unwrap_or(0)
+    }
+
+    /// Returns the ids of the seats taken.
+    pub fn taken_ids(&self) -> HashSet<usize> {
+        self.taken.keys().copied().collect()
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut taken_per_row = vec![0; self.codec.rows()];
+        for id in self.taken.keys() {
+            taken_per_row[self.codec.seat(*id).row()] += 1;
+        }
+
+        let mut lines_per_pass: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for pass in &self.passes {
+            lines_per_pass.entry(pass.text.clone()).or_default().push(pass.line);
+        }
+        lines_per_pass.retain(|_, lines| lines.len() > 1);
+
+        Stats {
+            num_passes: self.passes.len(),
+            num_taken: self.taken.len(),
+            first_row: taken_per_row.iter().position(|n| *n > 0),
+            last_row: taken_per_row.iter().rposition(|n| *n > 0),
+            taken_per_row,
+            duplicates: lines_per_pass,
+        }
+    }
+
+    /// Draws the seats of the plane, row by row, as '#' when taken and
+    /// '.' when free, an aisle being left before each of the 0-based
+    /// columns of |aisles|. The free seats between two taken ones are
+    /// drawn as 'O', and the seats with several passes as '2' to '9',
+    /// or '+'. When |colored|, these are highlighted with ANSI colors.
+    pub fn render(&self, aisles: &[usize], colored: bool) -> String {
+        let gaps = find_gaps(&self.taken_ids());
+        let cols = self.codec.cols();
+        let width = (self.codec.rows() - 1).to_string().len();
+        let mut res = String::new();
+
+        let paint = |res: &mut String, c: char, color: &str| {
+            if colored && !color.is_empty() {
+                let _ = write!(res, "\x1b[{}m{}\x1b[0m", color, c);
+            } else {
+                res.push(c);
+            }
+        };
+
+        for row in 0..self.codec.rows() {
+            let _ = write!(res, "{:>width$} ", row, width = width);
+            for col in 0..cols {
+                let id = self.codec.id(&Seat::from(row, col));
+                match self.num_passes(id) {
+                    0 if gaps.binary_search(&id).is_ok() =>
+                        paint(&mut res, 'O', "1;31"),
+                    0 =>
+                        paint(&mut res, '.', "2"),
+                    1 =>
+                        paint(&mut res, '#', ""),
+                    n =>
+                        paint(&mut res, std::char::from_digit(n as u32, 10).unwrap_or('+'), "1;33"),
+                }
+                if aisles.contains(&(col + 1)) && col + 1 < cols {
+                    res.push(' ');
+                }
+            }
+            res.push('\n');
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flight(text: &str, codec: &Codec, rows: Option<RangeInclusive<usize>>) -> Flight {
+        Flight::from_input(&Input::from_string("passes", text.to_string()), codec, rows).unwrap()
+    }
+
+    fn issues(flight: &Flight) -> Vec<(usize, Issue)> {
+        flight.diagnostics.iter().map(|d| (d.line, d.issue.clone())).collect()
+    }
+
+    #[test]
+    fn duplicates() {
+        let flight = flight("FBFBBFFRLR\nBFFFBBFRRR\n\nFBFBBFFRLR\nFBFBBFFRLR\n", &Codec::default(), None);
+        assert_eq!(issues(&flight), vec![(4, Issue::Duplicate(1)), (5, Issue::Duplicate(1))]);
+        assert_eq!(flight.passes.len(), 4);
+        assert_eq!(flight.num_passes(357), 3);
+        assert_eq!(flight.taken_ids().len(), 2);
+    }
+
+    #[test]
+    fn conflicts() {
+        let codec = Codec::default().ignoring_case();
+        let flight = flight("FBFBBFFRLR\nfbfbbffrlr\nFBFBBFFRLR\nBFFFBBFRRR\n", &codec, None);
+        assert_eq!(issues(&flight), vec![
+            (2, Issue::Conflict(1, "FBFBBFFRLR".to_string())),
+            (3, Issue::Duplicate(1)),
+        ]);
+        assert_eq!(flight.diagnostics[0].to_string(),
+            "2: seat already taken by pass 'FBFBBFFRLR' on line 1 (found 'fbfbbffrlr')");
+        assert_eq!(flight.num_passes(357), 3);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let flight = flight("FFFFFFFLLL\nFBFBBFFRLR\nBBBBBBBRRR\nFFFFFFFLLL\n", &Codec::default(), Some(1..=126));
+        assert_eq!(issues(&flight), vec![
+            (1, Issue::OutOfRange(0)),
+            (3, Issue::OutOfRange(127)),
+            (4, Issue::Duplicate(1)),
+            (4, Issue::OutOfRange(0)),
+        ]);
+        assert_eq!(flight.diagnostics[1].to_string(),
+            "3: seat is on row 127, which does not exist (found 'BBBBBBBRRR')");
+
+        // Every row exists unless told otherwise.
+        let flight = self::flight("FFFFFFFLLL\nBBBBBBBRRR\n", &Codec::default(), None);
+        assert!(flight.diagnostics.is_empty());
+    }
+
+    /// A plane of 4 rows of 4 seats: seats 1 and 4 are free between
+    /// taken ones, seat 5 has two passes and seat 15 has ten.
+    fn small_flight() -> Flight {
+        let codec = Codec::new(4, 4, ['F', 'B'], ['L', 'R']).unwrap();
+        let mut text = "FFLL\nFFRL\nFFRR\nFBLR\nFBLR\nFBRL\n".to_string();
+        text.push_str(&"BBRR\n".repeat(10));
+        flight(&text, &codec, None)
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(small_flight().render(&[2], false), "\
+0 #O ##
+1 O2 #.
+2 .. ..
+3 .. .+
+");
+        assert_eq!(small_flight().render(&[1, 3], false), "\
+0 # O# #
+1 O 2# .
+2 . .. .
+3 . .. +
+");
+        assert_eq!(small_flight().render(&[], true), concat!(
+            "0 #\x1b[1;31mO\x1b[0m##\n",
+            "1 \x1b[1;31mO\x1b[0m\x1b[1;33m2\x1b[0m#\x1b[2m.\x1b[0m\n",
+            "2 \x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\n",
+            "3 \x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\x1b[1;33m+\x1b[0m\n",
+        ));
+    }
+
+    #[test]
+    fn render_default_plane() {
+        let flight = flight("FFFFFFFLLL\nBBBBBBBRRR\n", &Codec::default(), None);
+        let map = flight.render(&[4], false);
+        let lines: Vec<&str> = map.lines().collect();
+        assert_eq!(lines.len(), 128);
+        assert_eq!(lines[0], "  0 #... ....");
+        assert_eq!(lines[9], "  9 .... ....");
+        assert_eq!(lines[127], "127 .... ...#");
+    }
+
+    #[test]
+    fn stats() {
+        let stats = small_flight().stats();
+        assert_eq!(stats, Stats {
+            num_passes: 16,
+            num_taken: 6,
+            taken_per_row: vec![3, 2, 0, 1],
+            first_row: Some(0),
+            last_row: Some(3),
+            duplicates: vec![
+                ("BBRR".to_string(), (7..=16).collect()),
+                ("FBLR".to_string(), vec![4, 5]),
+            ].into_iter().collect(),
+        });
+
+        let codec = Codec::new(4, 4, ['F', 'B'], ['L', 'R']).unwrap();
+        let stats = flight("FBRL\nBFLL\n", &codec, None).stats();
+        assert_eq!((stats.first_row, stats.last_row), (Some(1), Some(2)));
+        assert!(stats.duplicates.is_empty());
+
+        let stats = flight("", &codec, None).stats();
+        assert_eq!((stats.num_passes, stats.num_taken), (0, 0));
+        assert_eq!((stats.first_row, stats.last_row), (None, None));
+    }
+
+    #[test]
+    fn invalid_passes() {
+        let res = Flight::from_input(&Input::from_string("passes", "FBFBBFFRLR\nFBFBBFFRL\n".to_string()),
+            &Codec::default(), None);
+        assert_eq!(res.unwrap_err().to_string(),
+            "passes:2:1: boarding pass must have 10 characters, not 9 (found 'FBFBBFFRL')");
+    }
+}
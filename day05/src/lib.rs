@@ -0,0 +1,145 @@
+use common::{Answer, Error, Input, Result, Solution};
+
+use std::collections::HashSet;
+
+pub mod codec;
+pub mod flight;
+
+pub use codec::{Codec, PassError};
+pub use flight::Flight;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Seat {
+    row: usize,
+    col: usize,
+}
+
+impl Seat {
+    /// Decodes a boarding pass of the puzzle, such as "FBFBBFFRLR".
+    pub fn from_pass(s: &str) -> Result<Seat> {
+        Codec::default().decode(s).map_err(|e| e.at(s))
+    }
+
+    pub fn from(row: usize, col: usize) -> Seat {
+        Seat {
+            row,
+            col,
+        }
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+}
+
+/// The seats that can be ours.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Gap {
+    None,
+    One(usize),
+    /// Several seats can be ours, by increasing id.
+    Ambiguous(Vec<usize>),
+}
+
+/// Returns every id missing from |ids| while both its neighbours, id - 1
+/// and id + 1, are in |ids|, by increasing id.
+pub fn find_gaps(ids: &HashSet<usize>) -> Vec<usize> {
+    let mut sorted: Vec<usize> = ids.iter().copied().collect();
+    sorted.sort_unstable();
+
+    sorted.windows(2)
+        .filter(|pair| pair[1] - pair[0] == 2)
+        .map(|pair| pair[0] + 1)
+        .collect()
+}
+
+/// Finds our seat: the only one missing from |ids| while both its
+/// neighbours are in |ids|.
+pub fn find_missing_id(ids: &HashSet<usize>) -> Gap {
+    let mut gaps = find_gaps(ids);
+    match gaps.len() {
+        0 => Gap::None,
+        1 => Gap::One(gaps.remove(0)),
+        _ => Gap::Ambiguous(gaps),
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Flight;
+
+    /// Decodes the boarding passes of the puzzle. Passes scanned twice
+    /// are kept, along with their diagnostics, see Flight.
+    fn parse(input: &Input) -> Result<Self::Input> {
+        Flight::from_input(input, &Codec::default(), None)
+    }
+
+    fn part1(flight: &Self::Input) -> Result<Answer> {
+        let max_id = flight.passes.iter()
+            .map(|pass| pass.id)
+            .max()
+            .ok_or_else(|| Error::Unsolvable("no boarding pass".to_string()))?;
+        Ok(max_id.into())
+    }
+
+    fn part2(flight: &Self::Input) -> Result<Answer> {
+        match find_missing_id(&flight.taken_ids()) {
+            Gap::One(id) =>
+                Ok(id.into()),
+            Gap::None =>
+                Err(Error::Unsolvable("no free seat is between two taken ones".to_string())),
+            Gap::Ambiguous(ids) => {
+                let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+                Err(Error::Unsolvable(format!("ambiguous, the seat can be any of {}", ids.join(", "))))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Flight> {
+        Day05::parse(&Input::from_string("passes", text.to_string()))
+    }
+
+    #[test]
+    fn solve() {
+        let flight = parse("BFFFBBFRRR\nFFFBBBFRRR\n\nBBFFBBFRLL\n").unwrap();
+        assert_eq!(Day05::part1(&flight).unwrap(), 820.into());
+
+        let flight = parse("FBFBBFFRLL\nFBFBBFFRRL\n").unwrap();
+        assert_eq!(Day05::part2(&flight).unwrap(), 357.into());
+    }
+
+    #[test]
+    fn gaps() {
+        let ids = |ids: &[usize]| ids.iter().copied().collect::<HashSet<usize>>();
+        assert_eq!(find_missing_id(&ids(&[])), Gap::None);
+        assert_eq!(find_missing_id(&ids(&[3, 4, 5])), Gap::None);
+        assert_eq!(find_missing_id(&ids(&[3, 5, 6])), Gap::One(4));
+        assert_eq!(find_missing_id(&ids(&[1, 3, 5, 8])), Gap::Ambiguous(vec![2, 4]));
+    }
+
+    #[test]
+    fn duplicate_passes() {
+        let flight = parse("FBFBBFFRLR\nBFFFBBFRRR\nFBFBBFFRLR\n").unwrap();
+        assert_eq!(flight.diagnostics.len(), 1);
+        assert_eq!(flight.diagnostics[0].to_string(),
+            "3: duplicate pass, first scanned on line 1 (found 'FBFBBFFRLR')");
+        assert_eq!(Day05::part1(&flight).unwrap(), 567.into());
+    }
+
+    #[test]
+    fn invalid_passes() {
+        let error = parse("FBFBBFFRLR\nFBFBBFXRLR\n").unwrap_err();
+        assert_eq!(error.to_string(),
+            "passes:2:7: invalid row character, expected 'F' or 'B' (found 'X')");
+    }
+}
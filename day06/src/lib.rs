@@ -0,0 +1,87 @@
+use common::{Answer, Input, Result, Solution};
+
+pub mod query;
+
+pub use query::{Masks, Query};
+
+/// The answers of a group, one line per person.
+pub type Group = Vec<String>;
+
+/// Parses the groups of answers, separated by blank lines. Each
+/// answer must be a question from 'a' to 'z'.
+pub fn parse_groups(input: &Input) -> Result<Vec<Group>> {
+    let mut groups = Vec::new();
+    for record in input.records() {
+        let mut group = Group::new();
+        for line in &record.lines {
+            if let Some((i, c)) = line.text.chars().enumerate().find(|(_, c)| !c.is_ascii_lowercase()) {
+                return Err(input.error(line.number, i + 1, c.to_string(),
+                    "invalid answer, expected 'a' to 'z'"));
+            }
+            group.push(line.text.to_string());
+        }
+        groups.push(group);
+    }
+
+    Ok(groups)
+}
+
+/// Counts the questions answered by anyone, summed over the groups.
+pub fn count_num_questions(groups: &[Group]) -> usize {
+    let masks: Vec<Masks> = groups.iter().map(Masks::from_group).collect();
+    Query::any().count(&masks)
+}
+
+/// Counts the questions answered by everyone, summed over the groups.
+pub fn count_num_questions_part2(groups: &[Group]) -> usize {
+    let masks: Vec<Masks> = groups.iter().map(Masks::from_group).collect();
+    Query::all().count(&masks)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Group>;
+
+    fn parse(input: &Input) -> Result<Self::Input> {
+        parse_groups(input)
+    }
+
+    fn part1(groups: &Self::Input) -> Result<Answer> {
+        Ok(count_num_questions(groups).into())
+    }
+
+    fn part2(groups: &Self::Input) -> Result<Answer> {
+        Ok(count_num_questions_part2(groups).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Vec<Group>> {
+        parse_groups(&Input::from_string("answers", text.to_string()))
+    }
+
+    #[test]
+    fn example() {
+        let groups = parse("abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n").unwrap();
+        assert_eq!(groups.len(), 5);
+        assert_eq!(groups[2], vec!["ab".to_string(), "ac".to_string()]);
+        assert_eq!(count_num_questions(&groups), 11);
+        assert_eq!(count_num_questions_part2(&groups), 6);
+    }
+
+    #[test]
+    fn invalid_answers() {
+        let error = parse("abc\n\nab\naC\n").unwrap_err();
+        assert_eq!(error.to_string(), "answers:4:2: invalid answer, expected 'a' to 'z' (found 'C')");
+
+        let error = parse("ab\na b\n").unwrap_err();
+        assert_eq!(error.to_string(), "answers:2:2: invalid answer, expected 'a' to 'z' (found ' ')");
+
+        let error = parse("éa\n").unwrap_err();
+        assert_eq!(error.to_string(), "answers:1:1: invalid answer, expected 'a' to 'z' (found 'é')");
+    }
+}
@@ -0,0 +1,386 @@
+//! Queries over the answers of each group, as set algebra.
+//!
+//! The questions answered by a group are a 26-bit mask, one bit per
+//! letter, and a query combines such masks:
+//!
+//! - `any` and `all`: the questions answered by anyone, and by everyone;
+//! - `atleast(k)`, `atmost(k)` and `exactly(k)`: the questions answered
+//!   by at least, at most, or exactly k persons, `one` being
+//!   `exactly(1)`;
+//! - `person(i)`: the questions answered by the i-th person, from 1;
+//! - `[abc]`: the given questions;
+//! - `a | b`, `a & b`, `a - b` and `!a`: union, intersection,
+//!   difference and complement.
+//!
+//! `!` binds tighter than `&`, which binds tighter than `|` and `-`. For
+//! instance, `(person(1) | person(2)) - person(3)`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::{Error, Result};
+
+use crate::Group;
+
+/// Every question, from 'a' to 'z'.
+pub const ALL_QUESTIONS: u32 = (1 << 26) - 1;
+
+/// Returns the mask of the questions answered in |answers|. Characters
+/// other than 'a' to 'z' are ignored, see parse_groups() which rejects
+/// them.
+pub fn mask(answers: &str) -> u32 {
+    answers.bytes()
+        .filter(u8::is_ascii_lowercase)
+        .fold(0, |mask, c| mask | 1 << (c - b'a'))
+}
+
+/// The answers of a group, ready to be queried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Masks {
+    /// The questions answered by each person.
+    pub persons: Vec<u32>,
+    /// The questions answered by at least k persons, for k from 0 to
+    /// one more than the number of persons.
+    at_least: Vec<u32>,
+}
+
+impl Masks {
+    pub fn from_group(group: &Group) -> Masks {
+        let persons: Vec<u32> = group.iter().map(|answers| mask(answers)).collect();
+
+        // Each person raises the count of their questions by one.
+        let mut at_least = vec![0; persons.len() + 2];
+        at_least[0] = ALL_QUESTIONS;
+        for (i, person) in persons.iter().enumerate() {
+            for k in (1..=i + 1).rev() {
+                at_least[k] |= at_least[k - 1] & person;
+            }
+        }
+
+        Masks { persons, at_least }
+    }
+
+    /// Returns the questions answered by at least |k| persons.
+    pub fn at_least(&self, k: usize) -> u32 {
+        self.at_least.get(k).copied().unwrap_or(0)
+    }
+}
+
+/// A query, see the module documentation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Query {
+    All,
+    AtLeast(usize),
+    AtMost(usize),
+    Exactly(usize),
+    Person(usize),
+    Questions(u32),
+    Union(Box<Query>, Box<Query>),
+    Intersection(Box<Query>, Box<Query>),
+    Difference(Box<Query>, Box<Query>),
+    Complement(Box<Query>),
+}
+
+impl Query {
+    /// The questions answered by anyone.
+    pub fn any() -> Query {
+        Query::AtLeast(1)
+    }
+
+    /// The questions answered by everyone.
+    pub fn all() -> Query {
+        Query::All
+    }
+
+    /// Returns the questions of |masks| matching the query.
+    pub fn eval(&self, masks: &Masks) -> u32 {
+        match self {
+            Query::All =>
+                masks.at_least(masks.persons.len().max(1)),
+            Query::AtLeast(k) =>
+                masks.at_least(*k),
+            Query::AtMost(k) =>
+                masks.at_least(1) & !masks.at_least(k.saturating_add(1)),
+            Query::Exactly(k) =>
+                masks.at_least(*k) & !masks.at_least(k.saturating_add(1)),
+            Query::Person(i) =>
+                i.checked_sub(1)
+                    .and_then(|i| masks.persons.get(i))
+                    .copied()
+                    .unwrap_or(0),
+            Query::Questions(mask) =>
+                *mask,
+            Query::Union(a, b) =>
+                a.eval(masks) | b.eval(masks),
+            Query::Intersection(a, b) =>
+                a.eval(masks) & b.eval(masks),
+            Query::Difference(a, b) =>
+                a.eval(masks) & !b.eval(masks),
+            Query::Complement(a) =>
+                ALL_QUESTIONS & !a.eval(masks),
+        }
+    }
+
+    /// Returns the number of questions matching the query, summed over
+    /// every group of |groups|.
+    pub fn count(&self, groups: &[Masks]) -> usize {
+        groups.iter()
+            .map(|masks| self.eval(masks).count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Formats |mask| as its questions, such as "abc".
+pub fn questions(mask: u32) -> String {
+    (0..26u8)
+        .filter(|i| mask & 1 << i != 0)
+        .map(|i| (b'a' + i) as char)
+        .collect()
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Query::All => write!(f, "all"),
+            Query::AtLeast(k) => write!(f, "atleast({})", k),
+            Query::AtMost(k) => write!(f, "atmost({})", k),
+            Query::Exactly(k) => write!(f, "exactly({})", k),
+            Query::Person(i) => write!(f, "person({})", i),
+            Query::Questions(mask) => write!(f, "[{}]", questions(*mask)),
+            Query::Union(a, b) => write!(f, "({} | {})", a, b),
+            Query::Intersection(a, b) => write!(f, "({} & {})", a, b),
+            Query::Difference(a, b) => write!(f, "({} - {})", a, b),
+            Query::Complement(a) => write!(f, "!{}", a),
+        }
+    }
+}
+
+/// A recursive descent parser of queries.
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> Error {
+        let rest = self.text[self.pos..].trim_start();
+        let column = self.text.len() - rest.len() + 1;
+        Error::parse(column, rest, message)
+    }
+
+    /// Skips whitespace, and consumes |token| if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        let rest = self.text[self.pos..].trim_start();
+        self.pos = self.text.len() - rest.len();
+        if rest.starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", token)))
+        }
+    }
+
+    /// Parses a union or a difference of intersections.
+    fn expr(&mut self) -> Result<Query> {
+        let mut query = self.term()?;
+        loop {
+            if self.eat("|") {
+                query = Query::Union(Box::new(query), Box::new(self.term()?));
+            } else if self.eat("-") {
+                query = Query::Difference(Box::new(query), Box::new(self.term()?));
+            } else {
+                return Ok(query);
+            }
+        }
+    }
+
+    /// Parses an intersection of factors.
+    fn term(&mut self) -> Result<Query> {
+        let mut query = self.factor()?;
+        while self.eat("&") {
+            query = Query::Intersection(Box::new(query), Box::new(self.factor()?));
+        }
+        Ok(query)
+    }
+
+    /// Parses a "(k)" argument.
+    fn argument(&mut self) -> Result<usize> {
+        self.expect("(")?;
+        let rest = self.text[self.pos..].trim_start();
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let arg = rest[..len].parse::<usize>()
+            .map_err(|_| self.error("expected a number"))?;
+        self.pos = self.text.len() - rest.len() + len;
+        self.expect(")")?;
+        Ok(arg)
+    }
+
+    fn factor(&mut self) -> Result<Query> {
+        if self.eat("!") {
+            return Ok(Query::Complement(Box::new(self.factor()?)));
+        }
+        if self.eat("(") {
+            let query = self.expr()?;
+            self.expect(")")?;
+            return Ok(query);
+        }
+        if self.eat("[") {
+            let rest = &self.text[self.pos..];
+            let len = rest.find(']')
+                .ok_or_else(|| self.error("expected ']'"))?;
+            if let Some((i, c)) = rest[..len].char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+                self.pos += i;
+                return Err(self.error(&format!("invalid question '{}', expected 'a' to 'z'", c)));
+            }
+            self.pos += len + 1;
+            return Ok(Query::Questions(mask(&rest[..len])));
+        }
+
+        let rest = self.text[self.pos..].trim_start();
+        let len = rest.bytes().take_while(u8::is_ascii_alphabetic).count();
+        let query = match &rest[..len] {
+            "any" => Query::AtLeast(1),
+            "all" => Query::All,
+            "one" => Query::Exactly(1),
+            "atleast" | "atmost" | "exactly" | "person" => {
+                let name = &rest[..len];
+                self.pos = self.text.len() - rest.len() + len;
+                let arg = self.argument()?;
+                return Ok(match name {
+                    "atleast" => Query::AtLeast(arg),
+                    "atmost" => Query::AtMost(arg),
+                    "exactly" => Query::Exactly(arg),
+                    _ => Query::Person(arg),
+                });
+            },
+            _ => return Err(self.error("expected a query")),
+        };
+        self.pos = self.text.len() - rest.len() + len;
+        Ok(query)
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Query> {
+        let mut parser = Parser { text: s, pos: 0 };
+        let query = parser.expr()?;
+        if !parser.text[parser.pos..].trim().is_empty() {
+            return Err(parser.error("unexpected text after the query"));
+        }
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Query {
+        s.parse().unwrap()
+    }
+
+    /// Returns the column, text and message of the error parsing |s|.
+    fn parse_error(s: &str) -> (usize, String, String) {
+        match s.parse::<Query>() {
+            Err(Error::Parse { location, text, message }) =>
+                (location.column, text, message),
+            res =>
+                panic!("{:?}", res),
+        }
+    }
+
+    fn masks(group: &[&str]) -> Masks {
+        Masks::from_group(&group.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(parse("any"), Query::AtLeast(1));
+        assert_eq!(parse("all"), Query::All);
+        assert_eq!(parse("one"), Query::Exactly(1));
+        assert_eq!(parse("atleast(2)"), Query::AtLeast(2));
+        assert_eq!(parse("atmost( 3 )"), Query::AtMost(3));
+        assert_eq!(parse("exactly(0)"), Query::Exactly(0));
+        assert_eq!(parse(" person (1) "), Query::Person(1));
+        assert_eq!(parse("[cab]"), Query::Questions(0b111));
+        assert_eq!(parse("[]"), Query::Questions(0));
+    }
+
+    #[test]
+    fn precedence() {
+        let person = |i| Box::new(Query::Person(i));
+        assert_eq!(parse("person(1) | person(2) & person(3)"),
+            Query::Union(person(1), Box::new(Query::Intersection(person(2), person(3)))));
+        assert_eq!(parse("person(1) - person(2) | person(3)"),
+            Query::Union(Box::new(Query::Difference(person(1), person(2))), person(3)));
+        assert_eq!(parse("!person(1) & person(2)"),
+            Query::Intersection(Box::new(Query::Complement(person(1))), person(2)));
+        assert_eq!(parse("!(person(1) & person(2))"),
+            Query::Complement(Box::new(Query::Intersection(person(1), person(2)))));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for s in ["any", "!all & [xyz]", "(person(1) | person(2)) - person(3)", "atleast(2) - !!one"].iter() {
+            let query = parse(s);
+            assert_eq!(parse(&query.to_string()), query, "{}", query);
+        }
+        assert_eq!(parse("(person(1) | person(2)) - [zx]").to_string(), "((person(1) | person(2)) - [xz])");
+    }
+
+    #[test]
+    fn eval() {
+        let group = masks(&["abc", "abd", "ae"]);
+        let eval = |s: &str| questions(parse(s).eval(&group));
+
+        assert_eq!(eval("any"), "abcde");
+        assert_eq!(eval("all"), "a");
+        assert_eq!(eval("one"), "cde");
+        assert_eq!(eval("atleast(2)"), "ab");
+        assert_eq!(eval("atmost(2)"), "bcde");
+        assert_eq!(eval("exactly(0)"), "fghijklmnopqrstuvwxyz");
+        assert_eq!(eval("atleast(0)"), questions(ALL_QUESTIONS));
+        assert_eq!(eval("person(2)"), "abd");
+        assert_eq!(eval("person(0) | person(4)"), "");
+        assert_eq!(eval("(person(1) | person(2)) - person(3)"), "bcd");
+        assert_eq!(eval("!any & [aez]"), "z");
+    }
+
+    #[test]
+    fn count() {
+        let groups = vec![masks(&["abc"]), masks(&["a", "b", "c"]), masks(&["ab", "ac"])];
+        assert_eq!(Query::any().count(&groups), 9);
+        assert_eq!(Query::all().count(&groups), 4);
+        assert_eq!(Query::all().count(&[masks(&[])]), 0);
+    }
+
+    #[test]
+    fn malformed_queries() {
+        let error = |column: usize, text: &str, message: &str| (column, text.to_string(), message.to_string());
+
+        assert_eq!(parse_error(""), error(1, "", "expected a query"));
+        assert_eq!(parse_error("anything"), error(1, "anything", "expected a query"));
+        assert_eq!(parse_error("any |"), error(6, "", "expected a query"));
+        assert_eq!(parse_error("any all"), error(5, "all", "unexpected text after the query"));
+        assert_eq!(parse_error("(any"), error(5, "", "expected ')'"));
+        assert_eq!(parse_error("any)"), error(4, ")", "unexpected text after the query"));
+        assert_eq!(parse_error("atleast"), error(8, "", "expected '('"));
+        assert_eq!(parse_error("atleast(two)"), error(9, "two)", "expected a number"));
+        assert_eq!(parse_error("atleast(2"), error(10, "", "expected ')'"));
+        assert_eq!(parse_error("person(99999999999999999999999)"),
+            error(8, "99999999999999999999999)", "expected a number"));
+        assert_eq!(parse_error("[ab"), error(2, "ab", "expected ']'"));
+        assert_eq!(parse_error("any & [aBc]"), error(9, "Bc]", "invalid question 'B', expected 'a' to 'z'"));
+        assert_eq!(parse_error("!"), error(2, "", "expected a query"));
+    }
+}
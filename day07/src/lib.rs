@@ -1,36 +1,42 @@
 use regex::Regex;
+
+use common::{Answer, Error, Input, Result, Solution};
+
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::env;
-use std::io::BufReader;
-use std::io::prelude::*;
-use std::fs::File;
 
 #[derive(Debug)]
-struct BagIndexer {
+pub struct BagIndexer {
     bag_container_re: Regex,
     bag_contained_re: Regex,
     contained_by: HashMap<String, Vec<String>>,
     container_of: HashMap<String, Vec<(String, usize)>>,
 }
 
+impl Default for BagIndexer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BagIndexer {
     pub fn new() -> BagIndexer {
-        return BagIndexer {
+        BagIndexer {
             bag_container_re: Regex::new(r"(\w+ \w+) bags contain ").unwrap(),
             bag_contained_re: Regex::new(r"(\d) (\w+ \w+) bag").unwrap(),
             contained_by: HashMap::new(),
             container_of: HashMap::new(),
-        };
+        }
     }
 
     /// Adds a rule to the indexer.
-    pub fn add_rule(&mut self, rule: &str) {
-        let cap_container = self.bag_container_re.captures(&rule)
-            .expect("invalid rule: container bag misconstructed");
+    pub fn add_rule(&mut self, rule: &str) -> Result<()> {
+        let cap_container = self.bag_container_re.captures(rule)
+            .ok_or_else(|| Error::parse(1, rule,
+                "invalid rule: container bag misconstructed"))?;
         let bag_container = &cap_container[1];
 
-        for cap in self.bag_contained_re.captures_iter(&rule) {
+        for cap in self.bag_contained_re.captures_iter(rule) {
             let num = &cap[1].parse::<usize>().unwrap();
             let bag = &cap[2];
 
@@ -49,11 +55,13 @@ impl BagIndexer {
 
             }
         }
+
+        Ok(())
     }
 
     /// Returns the number of individual bags required inside |of|.
     pub fn count_num_bags_inside_of(&self, of: &str) -> usize {
-        return self.count_num_bags_inside_of_helper(of) - 1;
+        self.count_num_bags_inside_of_helper(of) - 1
     }
 
     /// Returns the number of individual bags that can eventually
@@ -63,7 +71,7 @@ impl BagIndexer {
         self.count_num_bags_container_of_helper(&of.to_string(), &mut set);
         // The set also includes the requested bag, so we should exclude
         // it from the result.
-        return set.len() - 1;
+        set.len() - 1
     }
 
     fn count_num_bags_inside_of_helper(&self, of: &str) -> usize {
@@ -75,7 +83,7 @@ impl BagIndexer {
             }
         }
 
-        return count;
+        count
     }
 
     fn count_num_bags_container_of_helper(&self, of: &String, res_set: &mut HashSet<String>) {
@@ -87,34 +95,35 @@ impl BagIndexer {
 
         if let Some(bags) = self.contained_by.get(of) {
             for bag in bags {
-                self.count_num_bags_container_of_helper(&bag, res_set);
+                self.count_num_bags_container_of_helper(bag, res_set);
             }
         }
     }
 }
 
-fn main() {
-    let args:Vec<String> = env::args()
-        .collect();
-    if args.len() != 2 {
-        println!("usage: {} [input_path]", args[0]);
-        return;
-    }
+/// The bag whose containers and contents are looked for.
+pub const TARGET_BAG: &str = "shiny gold";
+
+pub struct Day07;
 
+impl Solution for Day07 {
+    type Input = BagIndexer;
 
-    let f = File::open(&args[1])
-        .expect("could not open the input file");
+    fn parse(input: &Input) -> Result<Self::Input> {
+        let mut bag_indexer = BagIndexer::new();
+        for line in input.lines().filter(|line| !line.text.is_empty()) {
+            bag_indexer.add_rule(line.text)
+                .map_err(|e| input.locate(e, line.number))?;
+        }
 
-    let mut bag_indexer = BagIndexer::new();
-    for data in BufReader::new(f).lines() {
-        let rule = data.unwrap();
-        bag_indexer.add_rule(&rule);
+        Ok(bag_indexer)
     }
 
-    let res1 = bag_indexer.count_num_bags_container_of("shiny gold");
-    println!("res part 1: {}", res1);
+    fn part1(bag_indexer: &Self::Input) -> Result<Answer> {
+        Ok(bag_indexer.count_num_bags_container_of(TARGET_BAG).into())
+    }
 
-    let res2 = bag_indexer.count_num_bags_inside_of("shiny gold");
-    println!("res part 2: {}", res2);
+    fn part2(bag_indexer: &Self::Input) -> Result<Answer> {
+        Ok(bag_indexer.count_num_bags_inside_of(TARGET_BAG).into())
+    }
 }
-
@@ -1,61 +1,62 @@
-use std::env;
-use std::io::BufReader;
-use std::io::prelude::*;
-use std::fs::File;
+use common::{Answer, Error, Input, Result, Solution};
 
 #[derive(Clone, Copy, Debug)]
-enum Opcode {
+pub enum Opcode {
     Nop(i32),
     Acc(i32),
     Jmp(i32),
 }
 
-fn opcode_to_str(op: Opcode) -> String {
-    return match op {
+pub fn opcode_to_str(op: Opcode) -> String {
+    match op {
         Opcode::Nop(num) => format!("nop {:4}", num),
         Opcode::Acc(num) => format!("acc {:4}", num),
         Opcode::Jmp(num) => format!("jmp {:4}", num),
-    };
+    }
 }
 
 
-#[derive(Debug)]
-struct Machine {
+#[derive(Clone, Debug)]
+pub struct Machine {
     program: Vec<(Opcode, bool)>,
     reg_acc: i32,
     reg_pc: usize,
 }
 
 impl Machine {
-    pub fn from_file(path: &str) -> Machine {
-        let f = File::open(path)
-            .expect("could not open the input file");
-
+    /// Creates a Machine from the program of the puzzle input.
+    pub fn from_input(input: &Input) -> Result<Machine> {
         let mut program:Vec<(Opcode, bool)> = Vec::new();
 
-        for data in BufReader::new(f).lines() {
-            let opcode_str = data.unwrap();
+        for line in input.lines().filter(|line| !line.text.is_empty()) {
+            let opcode_str = line.text;
+            let (name, operand) = match opcode_str.find(' ') {
+                Some(ind) => (&opcode_str[..ind], &opcode_str[ind + 1..]),
+                None => (opcode_str, ""),
+            };
 
-            let num = &opcode_str[4..].parse::<i32>().
-                expect("malformed instruction (bad operand)");
+            let num = operand.parse::<i32>()
+                .map_err(|_| input.error(line.number, name.len() + 2, operand,
+                    "malformed instruction (bad operand)"))?;
 
-            match &opcode_str[0..3] {
+            match name {
                 "acc" =>
-                    program.push( (Opcode::Acc(*num), false) ),
+                    program.push( (Opcode::Acc(num), false) ),
                 "jmp" =>
-                    program.push( (Opcode::Jmp(*num), false) ),
+                    program.push( (Opcode::Jmp(num), false) ),
                 "nop" =>
-                    program.push( (Opcode::Nop(*num), false) ),
+                    program.push( (Opcode::Nop(num), false) ),
                 _ =>
-                    panic!("malformed instruction (unknown opcode)"),
+                    return Err(input.error(line.number, 1, name,
+                        "malformed instruction (unknown opcode)")),
             }
         }
 
-        return Machine {
-            program: program,
+        Ok(Machine {
+            program,
             reg_acc: 0,
             reg_pc: 0,
-        };
+        })
     }
 
     /// Resets the machine internal state, except for the program.
@@ -73,7 +74,7 @@ impl Machine {
         self.reset();
 
         while self.reg_pc < self.program.len() {
-            let mut next_acc = self.reg_acc as i32;
+            let mut next_acc = self.reg_acc;
             let mut next_pc = self.reg_pc as i32;
 
             let opcode = &self.program[self.reg_pc];
@@ -93,7 +94,7 @@ impl Machine {
                 (Opcode::Jmp(num), false) => {
                     next_pc += num;
                 },
-                (op, true) => {
+                (_op, true) => {
                     return false;
                 },
             }
@@ -103,7 +104,7 @@ impl Machine {
             self.reg_pc = next_pc as usize;
         }
 
-        return true;
+        true
     }
 
     /// Patches the program to run completely.
@@ -142,7 +143,7 @@ impl Machine {
             }
         }
 
-        return false;
+        false
     }
 
     pub fn get_acc(&self) -> i32 {
@@ -154,23 +155,28 @@ impl Machine {
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args()
-        .collect();
-    if args.len() != 2 {
-        println!("usage: {} [path]", args[0]);
-        return;
-    }
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Machine;
 
-    let mut machine = Machine::from_file(&args[1]);
+    fn parse(input: &Input) -> Result<Self::Input> {
+        Machine::from_input(input)
+    }
 
-    println!("-- part 1 --");
-    let success = machine.run(false);
-    println!("success: {}", success);
-    println!("acc: {}", machine.get_acc());
+    fn part1(machine: &Self::Input) -> Result<Answer> {
+        // The original program never completes, the answer is the
+        // accumulator right before the infinite loop is detected.
+        let mut machine = machine.clone();
+        machine.run(false);
+        Ok(machine.get_acc().into())
+    }
 
-    println!("-- part 2 --");
-    let success = machine.patch_program();
-    println!("success: {}", success);
-    println!("acc: {}", machine.get_acc());
+    fn part2(machine: &Self::Input) -> Result<Answer> {
+        let mut machine = machine.clone();
+        if !machine.patch_program() {
+            return Err(Error::Unsolvable("could not patch the program".to_string()));
+        }
+        Ok(machine.get_acc().into())
+    }
 }
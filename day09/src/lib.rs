@@ -1,15 +1,13 @@
+use common::{Answer, Error, Input, Result, Solution};
+
 use std::collections::HashSet;
-use std::env;
-use std::fs::File;
-use std::io::BufReader;
-use std::io::prelude::*;
 
 /**
  * Implements circular buffer with fast lookup for item existence
  * at the expense of memory usage.
  */
 #[derive(Debug)]
-struct CircularBuffer {
+pub struct CircularBuffer {
     beg_index: usize,
     num_items: usize,
     buf: Vec<usize>,
@@ -18,12 +16,12 @@ struct CircularBuffer {
 
 impl CircularBuffer {
     pub fn new(capacity: usize) -> CircularBuffer {
-        return CircularBuffer {
+        CircularBuffer {
             beg_index: 0,
             num_items: 0,
             buf: vec![0; capacity],
             set: HashSet::new(),
-        };
+        }
     }
 
     pub fn capacity(&self) -> usize {
@@ -65,7 +63,7 @@ impl CircularBuffer {
     }
 }
 
-fn find_breaking_num(arr: &[usize]) -> Option<usize> {
+pub fn find_breaking_num(arr: &[usize]) -> Option<usize> {
     let mut buf = CircularBuffer::new(25);
 
     for num in arr {
@@ -79,11 +77,11 @@ fn find_breaking_num(arr: &[usize]) -> Option<usize> {
     None
 }
 
-fn find_subarray_sums_to(arr: &[usize], to: usize) -> Option<(usize, usize)> {
+pub fn find_subarray_sums_to(arr: &[usize], to: usize) -> Option<(usize, usize)> {
     for i in 0..arr.len() {
         let mut sum = arr[i];
-        for j in (i + 1)..arr.len() {
-            sum += arr[j];
+        for (j, num) in arr.iter().enumerate().skip(i + 1) {
+            sum += num;
             if sum == to {
                 return Some((i, j));
             }
@@ -93,36 +91,36 @@ fn find_subarray_sums_to(arr: &[usize], to: usize) -> Option<(usize, usize)> {
     None
 }
 
-fn main() {
-    let args:Vec<String> = env::args()
-        .collect();
-    if args.len() != 2 {
-        println!("usage: {} [path]", args[0]);
-        return;
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &Input) -> Result<Self::Input> {
+        input.parse_lines()
     }
 
-    let f = File::open(&args[1])
-        .expect("could not open input file");
+    fn part1(input_nums: &Self::Input) -> Result<Answer> {
+        let breaking_num = find_breaking_num(input_nums)
+            .ok_or_else(|| Error::Unsolvable("could not find breaking number".to_string()))?;
+        Ok(breaking_num.into())
+    }
 
-    let input_nums:Vec<usize> = BufReader::new(f)
-        .lines()
-        .map(|line| line.unwrap().parse().unwrap())
-        .collect();
+    fn part2(input_nums: &Self::Input) -> Result<Answer> {
+        let breaking_num = find_breaking_num(input_nums)
+            .ok_or_else(|| Error::Unsolvable("could not find breaking number".to_string()))?;
 
-    let breaking_num = find_breaking_num(&input_nums)
-        .expect("could not find breaking number");
+        let (i, j) = find_subarray_sums_to(input_nums, breaking_num)
+            .ok_or_else(|| Error::Unsolvable("could not find a subarray that sums to the breaking num".to_string()))?;
 
-    let (i, j) = find_subarray_sums_to(&input_nums, breaking_num)
-        .expect("could not find a subarray that sums to the breaking num");
+        // Find the min/max.
+        let mut min = usize::MAX;
+        let mut max = usize::MIN;
+        for i in &input_nums[i..j] {
+            min = std::cmp::min(min, *i);
+            max = std::cmp::max(max, *i);
+        }
 
-    // Find the min/max.
-    let mut min = usize::MAX;
-    let mut max = usize::MIN;
-    for i in &input_nums[i..j] {
-        min = std::cmp::min(min, *i);
-        max = std::cmp::max(max, *i);
+        Ok((min + max).into())
     }
-
-    println!("answer part 1: {}", breaking_num);
-    println!("answer part 2: {}", min + max);
 }
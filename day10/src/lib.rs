@@ -1,15 +1,12 @@
-use std::env;
-use std::io::BufReader;
-use std::io::prelude::*;
-use std::fs::File;
+use common::{Answer, Input, Result, Solution};
 
-fn calc_part1(arr: &[usize]) -> (usize, usize) {
+pub fn calc_part1(arr: &[usize]) -> (usize, usize) {
     let mut n1 = 0;
     let mut n3 = 0;
     let mut prev = 0;
 
     for curr in arr {
-        match (*curr - prev) {
+        match *curr - prev  {
             1 => n1 += 1,
             3 => n3 += 1,
             _ => {},
@@ -17,7 +14,7 @@ fn calc_part1(arr: &[usize]) -> (usize, usize) {
         prev = *curr;
     }
 
-    return (n1, n3);
+    (n1, n3)
 }
 
 /*
@@ -33,7 +30,7 @@ fn calc_part1(arr: &[usize]) -> (usize, usize) {
  * The solution of part 2 is then F(n), and can be calculated by applying
  * a bottom-up dynamic programming approach.
  */
-fn calc_part2(arr: &[usize]) -> usize {
+pub fn calc_part2(arr: &[usize]) -> usize {
     let mut sol:Vec<usize> = vec![0; arr.len()];
 
     sol[0] = 1;
@@ -49,31 +46,34 @@ fn calc_part2(arr: &[usize]) -> usize {
         sol[i] = res;
     }
 
-    return sol[sol.len() - 1];
+    sol[sol.len() - 1]
 }
 
-fn main() {
-    let args:Vec<String> = env::args()
-        .collect();
-    if args.len() != 2 {
-        println!("usage: {} [path]", args[0]);
-        return;
-    }
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &Input) -> Result<Self::Input> {
+        let mut arr = input.parse_lines::<usize>()?;
+        if arr.is_empty() {
+            return Err(input.error(1, 1, "", "no adapter"));
+        }
 
-    let f = File::open(&args[1])
-        .expect("could not open input file");
-    let mut arr:Vec<usize> = BufReader::new(f)
-        .lines()
-        .map(|l| l.unwrap().parse::<usize>().unwrap())
-        .collect();
-    // Sort the input and add the outlet and the device joltages.
-    arr.sort();
-    arr.insert(0, 0);
-    arr.insert(arr.len(), arr.last().unwrap() + 3);
+        // Sort the input and add the outlet and the device joltages.
+        arr.sort_unstable();
+        arr.insert(0, 0);
+        arr.push(arr.last().unwrap() + 3);
 
-    let (n1, n3) = calc_part1(&arr);
-    println!("answer part1: n1={}, n3={}, res={}", n1, n3, n1 * n3);
+        Ok(arr)
+    }
+
+    fn part1(arr: &Self::Input) -> Result<Answer> {
+        let (n1, n3) = calc_part1(arr);
+        Ok((n1 * n3).into())
+    }
 
-    let num_order = calc_part2(&arr);
-    println!("answer part2: {}", num_order);
+    fn part2(arr: &Self::Input) -> Result<Answer> {
+        Ok(calc_part2(arr).into())
+    }
 }
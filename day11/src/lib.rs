@@ -1,9 +1,7 @@
-use std::env;
-use std::io::prelude::*;
-use std::fs::File;
+use common::{Answer, Input, Result, Solution};
 
 #[derive(PartialEq, Copy, Clone)]
-enum Item {
+pub enum Item {
     Empty,
     Floor,
     Occupied,
@@ -25,39 +23,32 @@ impl Item {
 }
 
 #[derive(Clone)]
-struct Grid {
+pub struct Grid {
     w: usize,
     h: usize,
     data: Vec<Item>,
 }
 
 impl Grid {
-    pub fn from(s: &String) -> Result<Grid, &str> {
-        let h = s.matches('\n').count();
-        if h == 0 {
-            return Err("grid height must be > 0");
-        }
-
-        let w = (s.chars().count() / h) - 1;
-        if w == 0 {
-            return Err("grid width must be > 0");
-        }
-
-        let mut data:Vec<Item> = vec![Item::Floor; w * h];
-        let mut data_ind = 0;
-
-        for c in s.chars() {
-            if let Some(item) = Item::from_char(c) {
-                data[data_ind] = item;
-                data_ind += 1;
+    /// Creates a Grid from the puzzle input.
+    pub fn from_input(input: &Input) -> Result<Grid> {
+        let grid = input.grid()?;
+
+        let mut data:Vec<Item> = Vec::with_capacity(grid.cells.len());
+        for y in 0..grid.height {
+            for (x, c) in grid.row(y).iter().enumerate() {
+                let item = Item::from_char(*c as char)
+                    .ok_or_else(|| input.error(y + 1, x + 1, (*c as char).to_string(),
+                        "unknown item"))?;
+                data.push(item);
             }
         }
 
-        return Ok(Grid {
-            w: w,
-            h: h,
-            data: data,
-        });
+        Ok(Grid {
+            w: grid.width,
+            h: grid.height,
+            data,
+        })
     }
 
     pub fn get_num_occupied(&self) -> usize {
@@ -74,7 +65,7 @@ impl Grid {
             }
         }
 
-        return true;
+        true
     }
 
     pub fn print(&self) {
@@ -89,7 +80,7 @@ impl Grid {
                         print!("#"),
                 }
             }
-            print!("\n");
+            println!();
         }
     }
 
@@ -97,11 +88,9 @@ impl Grid {
         assert_eq!(self.w, next_grid.w);
         assert_eq!(self.h, next_grid.h);
 
-        let dirs = vec![
-            (-1, -1), (0, -1), (1, -1),
+        let dirs = [(-1, -1), (0, -1), (1, -1),
             (-1, 0), (1, 0),
-            (-1, 1), (0, 1), (1, 1),
-        ];
+            (-1, 1), (0, 1), (1, 1)];
 
         for y in 0..self.h {
             for x in 0..self.w {
@@ -140,7 +129,7 @@ impl Grid {
     }
 }
 
-fn do_part1(origin: &Grid) -> usize {
+pub fn do_part1(origin: &Grid) -> usize {
     let mut g0 = origin.clone();
     let mut g1 = origin.clone();
 
@@ -162,25 +151,20 @@ fn do_part1(origin: &Grid) -> usize {
     }
 }
 
-fn main() {
-    let args:Vec<String> = env::args()
-        .collect();
-    if args.len() != 2 {
-        println!("usage: {} [path]", args[0]);
-        return;
-    }
+pub struct Day11;
 
-    let mut file = File::open(&args[1])
-        .expect("could not open input file");
+impl Solution for Day11 {
+    type Input = Grid;
 
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)
-        .expect("could not read input file");
+    fn parse(input: &Input) -> Result<Self::Input> {
+        Grid::from_input(input)
+    }
 
-    let input_grid = Grid::from(&buf)
-        .expect("could not create grid");
+    fn part1(grid: &Self::Input) -> Result<Answer> {
+        Ok(do_part1(grid).into())
+    }
 
-    let res_part1 = do_part1(&input_grid.clone());
-    println!("res_part1={}", res_part1);
+    fn part2(_grid: &Self::Input) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
 }
-